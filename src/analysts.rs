@@ -0,0 +1,164 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::yahoo::{self, raw_number};
+use crate::{Result, YahooClient};
+
+/// The quote summary modules the analyst opinions come from
+const MODULES: &[&str] = &["recommendationTrend", "upgradeDowngradeHistory", "financialData"];
+
+/// How many analysts recommend each action for a symbol over a month.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecommendationTrend {
+   /// The month, relative to this one, according to Yahoo.  ie. '0m' or '-1m'
+   pub period: String,
+
+   pub strong_buy: u32,
+   pub buy: u32,
+   pub hold: u32,
+   pub sell: u32,
+   pub strong_sell: u32
+}
+impl RecommendationTrend {
+   fn new(data: yahoo::RecommendationTrendData) -> Option<RecommendationTrend> {
+      Some(RecommendationTrend {
+         period: data.period?,
+         strong_buy: data.strong_buy,
+         buy: data.buy,
+         hold: data.hold,
+         sell: data.sell,
+         strong_sell: data.strong_sell
+      })
+   }
+}
+
+/// What an analyst firm did to its rating of a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GradeAction {
+   Upgrade,
+   Downgrade,
+   Initiated,
+   Reiterated,
+   Maintained,
+   Other(String)
+}
+impl GradeAction {
+   fn new(value: &str) -> GradeAction {
+      match value {
+         "up" => GradeAction::Upgrade,
+         "down" => GradeAction::Downgrade,
+         "init" => GradeAction::Initiated,
+         "reit" => GradeAction::Reiterated,
+         "main" => GradeAction::Maintained,
+         _ => GradeAction::Other(value.to_string())
+      }
+   }
+}
+
+/// An analyst firm changing (or confirming) its rating of a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GradeChange {
+   pub date: DateTime<Utc>,
+
+   /// The analyst firm - ie. 'Morgan Stanley'
+   pub firm: String,
+
+   /// The rating before the change - empty when coverage is initiated
+   pub from_grade: Option<String>,
+   pub to_grade: Option<String>,
+
+   pub action: Option<GradeAction>
+}
+impl GradeChange {
+   fn new(data: yahoo::GradeChangeData) -> Option<GradeChange> {
+      Some(GradeChange {
+         date: Utc.timestamp(data.epoch_grade_date?, 0),
+         firm: data.firm?,
+         from_grade: data.from_grade.filter(|grade| !grade.is_empty()),
+         to_grade: data.to_grade.filter(|grade| !grade.is_empty()),
+         action: data.action.as_deref().map(GradeAction::new)
+      })
+   }
+}
+
+/// The consensus of analyst price targets for a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceTarget {
+   pub low: Option<f64>,
+   pub mean: Option<f64>,
+   pub median: Option<f64>,
+   pub high: Option<f64>,
+
+   /// The number of analysts with an opinion on the symbol
+   pub analysts: Option<u32>,
+
+   /// The mean recommendation from 1 (strong buy) to 5 (strong sell)
+   pub recommendation_mean: Option<f64>,
+
+   /// The mean recommendation as Yahoo! describes it - ie. 'buy'
+   pub recommendation: Option<String>
+}
+impl PriceTarget {
+   fn new(data: yahoo::FinancialData) -> PriceTarget {
+      PriceTarget {
+         low: raw_number(data.target_low_price),
+         mean: raw_number(data.target_mean_price),
+         median: raw_number(data.target_median_price),
+         high: raw_number(data.target_high_price),
+         analysts: raw_number(data.number_of_analyst_opinions).map(|count| count as u32),
+         recommendation_mean: raw_number(data.recommendation_mean),
+         recommendation: data.recommendation_key.filter(|key| key != "none")
+      }
+   }
+}
+
+/// What analysts think of a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysts {
+   /// Recommendation counts for the last few months, most recent first
+   pub trend: Vec<RecommendationTrend>,
+
+   /// Rating changes by analyst firms, most recent first
+   pub grade_changes: Vec<GradeChange>,
+
+   pub price_target: Option<PriceTarget>
+}
+impl Analysts {
+   fn new(data: yahoo::QuoteSummaryData) -> Analysts {
+      Analysts {
+         trend: data.recommendation_trend.map_or_else(Vec::new, |trend| trend.trend.into_iter().filter_map(RecommendationTrend::new).collect()),
+         grade_changes: data.upgrade_downgrade_history.map_or_else(Vec::new, |history| history.history.into_iter().filter_map(GradeChange::new).collect()),
+         price_target: data.financial_data.map(PriceTarget::new)
+      }
+   }
+}
+
+/// Retrieves analyst recommendations, rating changes and price targets for a
+/// symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::analysts;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = analysts::retrieve("AAPL").await.unwrap();
+///
+///    if let Some(target) = data.price_target {
+///       println!("{:?} analysts have a mean target of {:?}", target.analysts, target.mean);
+///    }
+///    for change in data.grade_changes.iter().take(5) {
+///       println!("{}: {} {:?} -> {:?}", change.date.format("%b %e %Y"), change.firm, change.from_grade, change.to_grade);
+///    }
+/// }
+/// ```
+pub async fn retrieve(symbol: &str) -> Result<Analysts> {
+   YahooClient::shared()?.analysts(symbol).await
+}
+
+impl YahooClient {
+   /// Retrieves what analysts think of a symbol - see [`analysts::retrieve`](analysts/fn.retrieve.html).
+   pub async fn analysts(&self, symbol: &str) -> Result<Analysts> {
+      Ok(Analysts::new(yahoo::load_summary(self, symbol, MODULES).await?))
+   }
+}
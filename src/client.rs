@@ -0,0 +1,382 @@
+use once_cell::sync::OnceCell;
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, USER_AGENT};
+use reqwest::{Proxy, Response, StatusCode, Url};
+use snafu::{ensure, ResultExt};
+use std::time::Duration;
+use tokio::time::delay_for;
+
+use crate::session::{Session, SessionCache};
+use crate::throttle::{RateLimiter, RetryPolicy};
+use crate::{error, Result};
+
+const CHART_URL: &str = "https://query1.finance.yahoo.com/v8/finance/chart/";
+const OPTIONS_URL: &str = "https://query1.finance.yahoo.com/v7/finance/options/";
+const QUOTE_PAGE_URL: &str = "https://finance.yahoo.com/";
+const QUOTE_URL: &str = "https://query1.finance.yahoo.com/v7/finance/quote";
+const QUOTE_SUMMARY_URL: &str = "https://query2.finance.yahoo.com/v10/finance/quoteSummary/";
+const STREAMER_URL: &str = "wss://streamer.finance.yahoo.com/";
+const COOKIE_URL: &str = "https://fc.yahoo.com/";
+const CRUMB_URL: &str = "https://query1.finance.yahoo.com/v1/test/getcrumb";
+const CONCURRENCY: usize = 8;
+
+/// The client behind the free functions - created on first use
+static SHARED: OnceCell<YahooClient> = OnceCell::new();
+
+/// Helper function to parse a base URL that other paths are joined onto
+fn parse_base(url: &str) -> Result<Url> {
+   let mut base = Url::parse(url).context(error::InvalidURL { url })?;
+   if !base.path().ends_with('/') {
+      let path = format!("{}/", base.path());
+      base.set_path(&path);
+   }
+   Ok(base)
+}
+
+/// Configures a `YahooClient`
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yahoo_finance::YahooClient;
+///
+/// let client = YahooClient::builder()
+///    .timeout(Duration::from_secs(10))
+///    .user_agent("my-backtester/1.0")
+///    .build()
+///    .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct YahooClientBuilder {
+   chart_url: String,
+   options_url: String,
+   quote_page_url: String,
+   quote_url: String,
+   quote_summary_url: String,
+   streamer_url: String,
+   cookie_url: String,
+   crumb_url: String,
+   sessions: bool,
+   retry: RetryPolicy,
+   rate_limit: Option<(f64, u32)>,
+   concurrency: usize,
+   timeout: Option<Duration>,
+   user_agent: Option<String>,
+   proxy: Option<Proxy>,
+   default_headers: HeaderMap,
+   pooling: bool
+}
+impl YahooClientBuilder {
+   fn new() -> YahooClientBuilder {
+      YahooClientBuilder {
+         chart_url: CHART_URL.to_string(),
+         options_url: OPTIONS_URL.to_string(),
+         quote_page_url: QUOTE_PAGE_URL.to_string(),
+         quote_url: QUOTE_URL.to_string(),
+         quote_summary_url: QUOTE_SUMMARY_URL.to_string(),
+         streamer_url: STREAMER_URL.to_string(),
+         cookie_url: COOKIE_URL.to_string(),
+         crumb_url: CRUMB_URL.to_string(),
+         sessions: true,
+         retry: RetryPolicy::default(),
+         rate_limit: None,
+         concurrency: CONCURRENCY,
+         timeout: None,
+         user_agent: None,
+         proxy: None,
+         default_headers: HeaderMap::new(),
+         pooling: true
+      }
+   }
+
+   /// The base URL of the chart API - symbols are appended to it.
+   pub fn chart_url(mut self, url: &str) -> YahooClientBuilder {
+      self.chart_url = url.to_string();
+      self
+   }
+
+   /// The base URL of the options API - symbols are appended to it.
+   pub fn options_url(mut self, url: &str) -> YahooClientBuilder {
+      self.options_url = url.to_string();
+      self
+   }
+
+   /// The base URL of the Yahoo! Finance website quote pages are loaded from.
+   pub fn quote_page_url(mut self, url: &str) -> YahooClientBuilder {
+      self.quote_page_url = url.to_string();
+      self
+   }
+
+   /// The URL of the quote API snapshots are loaded from.
+   pub fn quote_url(mut self, url: &str) -> YahooClientBuilder {
+      self.quote_url = url.to_string();
+      self
+   }
+
+   /// The base URL of the quote summary API - symbols are appended to it.
+   pub fn quote_summary_url(mut self, url: &str) -> YahooClientBuilder {
+      self.quote_summary_url = url.to_string();
+      self
+   }
+
+   /// The websocket URL realtime quotes are streamed from.
+   pub fn streamer_url(mut self, url: &str) -> YahooClientBuilder {
+      self.streamer_url = url.to_string();
+      self
+   }
+
+   /// Whether to start a Yahoo! session (cookie & crumb) and send it along with
+   /// every call.  Sessions are on by default.
+   pub fn sessions(mut self, enabled: bool) -> YahooClientBuilder {
+      self.sessions = enabled;
+      self
+   }
+
+   /// The URL that hands out the session cookie.
+   pub fn cookie_url(mut self, url: &str) -> YahooClientBuilder {
+      self.cookie_url = url.to_string();
+      self
+   }
+
+   /// The URL that hands out the crumb for a session cookie.
+   pub fn crumb_url(mut self, url: &str) -> YahooClientBuilder {
+      self.crumb_url = url.to_string();
+      self
+   }
+
+   /// How calls are retried when Yahoo! is throttling us or failing.
+   pub fn retry(mut self, policy: RetryPolicy) -> YahooClientBuilder {
+      self.retry = policy;
+      self
+   }
+
+   /// Limits the calls made by the client (and all of its clones) to an average
+   /// of `per_second`, allowing short bursts of up to `burst` calls.  There is
+   /// no limit by default.
+   pub fn rate_limit(mut self, per_second: f64, burst: u32) -> YahooClientBuilder {
+      self.rate_limit = Some((per_second, burst));
+      self
+   }
+
+   /// The most calls made at the same time when loading many symbols at once -
+   /// ie. `history::retrieve_many`.  Defaults to 8.
+   pub fn concurrency(mut self, limit: usize) -> YahooClientBuilder {
+      self.concurrency = limit.max(1);
+      self
+   }
+
+   /// The longest a single call to Yahoo! can take before failing.
+   pub fn timeout(mut self, timeout: Duration) -> YahooClientBuilder {
+      self.timeout = Some(timeout);
+      self
+   }
+
+   /// The `User-Agent` header sent with every call.
+   pub fn user_agent(mut self, agent: &str) -> YahooClientBuilder {
+      self.user_agent = Some(agent.to_string());
+      self
+   }
+
+   /// Routes every call through a proxy.
+   pub fn proxy(mut self, proxy: Proxy) -> YahooClientBuilder {
+      self.proxy = Some(proxy);
+      self
+   }
+
+   /// Headers sent with every call.
+   pub fn default_headers(mut self, headers: HeaderMap) -> YahooClientBuilder {
+      self.default_headers = headers;
+      self
+   }
+
+   /// Whether to keep idle connections open for reuse - on by default.  A
+   /// connection can't be reused once the runtime that opened it is gone, so
+   /// turn this off when calling from more than one runtime.
+   pub fn pooling(mut self, enabled: bool) -> YahooClientBuilder {
+      self.pooling = enabled;
+      self
+   }
+
+   pub fn build(self) -> Result<YahooClient> {
+      let mut headers = self.default_headers;
+      if let Some(agent) = &self.user_agent {
+         headers.insert(USER_AGENT, HeaderValue::from_str(agent).context(error::InvalidHeader { name: "User-Agent" })?);
+      }
+
+      let mut http = reqwest::Client::builder().default_headers(headers.clone());
+      if let Some(timeout) = self.timeout { http = http.timeout(timeout); }
+      if let Some(proxy) = self.proxy { http = http.proxy(proxy); }
+      if !self.pooling { http = http.pool_max_idle_per_host(0); }
+
+      let limiter = match self.rate_limit {
+         Some((per_second, burst)) => {
+            ensure!(per_second > 0.0, error::InvalidRateLimit { per_second });
+            Some(RateLimiter::new(per_second, burst))
+         },
+         None => None
+      };
+
+      let sessions = if self.sessions {
+         let cookie_url = Url::parse(&self.cookie_url).context(error::InvalidURL { url: &self.cookie_url })?;
+         let crumb_url = Url::parse(&self.crumb_url).context(error::InvalidURL { url: &self.crumb_url })?;
+         Some(SessionCache::new(cookie_url, crumb_url, limiter.clone()))
+      } else {
+         None
+      };
+
+      Ok(YahooClient {
+         http: http.build().context(error::ClientFailed)?,
+         sessions,
+         retry: self.retry,
+         limiter,
+         concurrency: self.concurrency,
+         headers,
+         chart_url: parse_base(&self.chart_url)?,
+         options_url: parse_base(&self.options_url)?,
+         quote_page_url: parse_base(&self.quote_page_url)?,
+         quote_url: Url::parse(&self.quote_url).context(error::InvalidURL { url: &self.quote_url })?,
+         quote_summary_url: parse_base(&self.quote_summary_url)?,
+         streamer_url: Url::parse(&self.streamer_url).context(error::InvalidURL { url: &self.streamer_url })?
+      })
+   }
+}
+
+/// A client for all calls to Yahoo!, sharing a single HTTP connection pool.
+///
+/// The free functions in this crate (ie. `history::retrieve`) all share a single
+/// client with the default configuration - except that it doesn't reuse
+/// connections, since they can't outlive the runtime that opened them.  Create
+/// your own (within a single runtime) to reuse connections, or to control timeouts,
+/// proxies, headers, etc.  Each client can also
+/// point at its own endpoints - ie. a local mock server when testing.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ Interval, YahooClient };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let client = YahooClient::new().unwrap();
+///    for symbol in &["AAPL", "MSFT"] {
+///       let data = client.history_interval(symbol, Interval::_5d).await.unwrap();
+///       println!("{} has {} days of data", symbol, data.len());
+///    }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct YahooClient {
+   http: reqwest::Client,
+   sessions: Option<SessionCache>,
+   retry: RetryPolicy,
+   limiter: Option<RateLimiter>,
+   concurrency: usize,
+   headers: HeaderMap,
+   chart_url: Url,
+   options_url: Url,
+   quote_page_url: Url,
+   quote_url: Url,
+   quote_summary_url: Url,
+   streamer_url: Url
+}
+impl YahooClient {
+   /// Creates a client with the default configuration
+   pub fn new() -> Result<YahooClient> { YahooClient::builder().build() }
+
+   pub fn builder() -> YahooClientBuilder { YahooClientBuilder::new() }
+
+   /// The client shared by the free functions - along with its session.
+   /// Creating it is retried on the next call if it fails.
+   ///
+   /// Pooled connections are tied to the runtime that opened them, and the
+   /// free functions may be called from many runtimes (ie. one `block_on` after
+   /// another) - so the shared client doesn't keep idle connections around.
+   pub(crate) fn shared() -> Result<&'static YahooClient> {
+      SHARED.get_or_try_init(|| YahooClient::builder().pooling(false).build())
+   }
+
+   pub(crate) fn concurrency(&self) -> usize { self.concurrency }
+
+   pub(crate) fn chart_url(&self) -> &Url { &self.chart_url }
+
+   pub(crate) fn options_url(&self) -> &Url { &self.options_url }
+
+   pub(crate) fn quote_page_url(&self) -> &Url { &self.quote_page_url }
+
+   pub(crate) fn quote_url(&self) -> &Url { &self.quote_url }
+
+   pub(crate) fn quote_summary_url(&self) -> &Url { &self.quote_summary_url }
+
+   pub(crate) fn streamer_url(&self) -> &Url { &self.streamer_url }
+
+   /// The headers to send with every call - including the user agent
+   pub(crate) fn headers(&self) -> &HeaderMap { &self.headers }
+
+   /// Makes a call to Yahoo!, failing on anything other than a success.
+   ///
+   /// When sessions are on, the session goes along with the call and a new
+   /// session is started if Yahoo! rejects the current one.  Calls go out
+   /// without a session if one can't be started, failing only if Yahoo! then
+   /// rejects them.  Throttled or failed calls are retried according to the
+   /// retry policy.
+   pub(crate) async fn get(&self, url: &Url) -> Result<Response> {
+      let mut attempt = 0;
+      let mut response = self.send_with_session(url).await?;
+      while self.retry.should_retry(&response, attempt) {
+         delay_for(self.retry.delay(&response, attempt)).await;
+         attempt += 1;
+         response = self.send_with_session(url).await?;
+      }
+
+      ensure!(
+         response.status().is_success(),
+         error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
+      );
+
+      Ok(response)
+   }
+
+   async fn send_with_session(&self, url: &Url) -> Result<Response> {
+      let session = match &self.sessions {
+         Some(sessions) => sessions.get(&self.http).await,
+         None => None
+      };
+
+      let response = self.send(url, session.as_ref()).await?;
+      if let Some(sessions) = &self.sessions {
+         if response.status() == StatusCode::UNAUTHORIZED || response.status() == StatusCode::FORBIDDEN {
+            // Yahoo! insists on a (new) session - so not getting one fails the call
+            let session = sessions.renew(&self.http, session.as_ref()).await?;
+            return self.send(url, Some(&session)).await;
+         }
+      }
+      Ok(response)
+   }
+
+   async fn send(&self, url: &Url, session: Option<&Session>) -> Result<Response> {
+      if let Some(limiter) = &self.limiter { limiter.acquire().await; }
+
+      let mut url = url.clone();
+      if let Some(Session { crumb, .. }) = session {
+         url.query_pairs_mut().append_pair("crumb", crumb);
+      }
+
+      let mut request = self.http.get(url);
+      if let Some(Session { cookie, .. }) = session {
+         request = request.header(COOKIE, cookie.as_str());
+      }
+
+      // make the call - we do not really expect this to fail.
+      // ie - we won't 404 if the symbol doesn't exist
+      request.send().await.context(error::RequestFailed)
+   }
+}
+impl Default for YahooClient {
+   /// Creates a client with the default configuration
+   ///
+   /// # Panics
+   ///
+   /// This will panic if the HTTP client cannot be initialized - use
+   /// `YahooClient::new()` to handle the failure instead.
+   fn default() -> YahooClient { YahooClient::new().expect("failed to create the default Yahoo! client") }
+}
@@ -0,0 +1,169 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::yahoo::{self, raw_date, raw_number};
+use crate::{Result, YahooClient};
+
+/// The quote summary modules the earnings come from
+const MODULES: &[&str] = &["earningsHistory", "calendarEvents", "earningsTrend"];
+
+/// Reported earnings per share for a past quarter, compared to what analysts
+/// expected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarningsResult {
+   /// The last day of the fiscal quarter
+   pub quarter: Option<DateTime<Utc>>,
+
+   /// How many quarters ago, according to Yahoo.  ie. '-1q'
+   pub period: Option<String>,
+
+   pub eps_actual: Option<f64>,
+   pub eps_estimate: Option<f64>,
+   pub eps_difference: Option<f64>,
+
+   /// The difference as a fraction of the estimate - ie. 0.05 is a 5% beat
+   pub surprise_percent: Option<f64>
+}
+impl EarningsResult {
+   fn new(data: yahoo::EarningsResultData) -> EarningsResult {
+      EarningsResult {
+         quarter: raw_date(data.quarter),
+         period: data.period,
+         eps_actual: raw_number(data.eps_actual),
+         eps_estimate: raw_number(data.eps_estimate),
+         eps_difference: raw_number(data.eps_difference),
+         surprise_percent: raw_number(data.surprise_percent)
+      }
+   }
+}
+
+/// The next earnings announcement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarningsCalendar {
+   /// When earnings are expected to be announced - a single date once the
+   /// company has confirmed it, otherwise the start and end of a range.
+   pub dates: Vec<DateTime<Utc>>,
+
+   pub eps_average: Option<f64>,
+   pub eps_low: Option<f64>,
+   pub eps_high: Option<f64>,
+   pub revenue_average: Option<f64>,
+   pub revenue_low: Option<f64>,
+   pub revenue_high: Option<f64>
+}
+impl EarningsCalendar {
+   fn new(data: yahoo::EarningsCalendarData) -> EarningsCalendar {
+      EarningsCalendar {
+         dates: data.earnings_date.into_iter().filter_map(|date| raw_date(Some(date))).collect(),
+         eps_average: raw_number(data.earnings_average),
+         eps_low: raw_number(data.earnings_low),
+         eps_high: raw_number(data.earnings_high),
+         revenue_average: raw_number(data.revenue_average),
+         revenue_low: raw_number(data.revenue_low),
+         revenue_high: raw_number(data.revenue_high)
+      }
+   }
+}
+
+/// The analyst consensus for a single figure - ie. EPS or revenue.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Consensus {
+   pub average: Option<f64>,
+   pub low: Option<f64>,
+   pub high: Option<f64>,
+
+   /// The actual figure for the same period a year earlier
+   pub year_ago: Option<f64>,
+
+   /// The expected growth over the year earlier figure - ie. 0.1 is 10%
+   pub growth: Option<f64>,
+
+   pub analysts: Option<u32>
+}
+impl Consensus {
+   fn new(year_ago: Option<yahoo::RawNumber>, data: yahoo::EstimateData) -> Consensus {
+      Consensus {
+         average: raw_number(data.avg),
+         low: raw_number(data.low),
+         high: raw_number(data.high),
+         year_ago: raw_number(year_ago),
+         growth: raw_number(data.growth),
+         analysts: raw_number(data.number_of_analysts).map(|count| count as u32)
+      }
+   }
+}
+
+/// What analysts expect for an upcoming quarter or fiscal year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EarningsEstimate {
+   /// The period being estimated, according to Yahoo.  ie. '0q', '+1q', '0y'
+   pub period: String,
+
+   /// The last day of the period
+   pub end_date: Option<NaiveDate>,
+
+   pub eps: Option<Consensus>,
+   pub revenue: Option<Consensus>
+}
+impl EarningsEstimate {
+   fn new(data: yahoo::EarningsTrendData) -> Option<EarningsEstimate> {
+      Some(EarningsEstimate {
+         period: data.period?,
+         end_date: data.end_date.and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()),
+         eps: data.earnings_estimate.map(|estimate| Consensus::new(estimate.year_ago_eps.clone(), estimate)),
+         revenue: data.revenue_estimate.map(|estimate| Consensus::new(estimate.year_ago_revenue.clone(), estimate))
+      })
+   }
+}
+
+/// Past and upcoming earnings for a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Earnings {
+   /// Results for the last few quarters, oldest first
+   pub history: Vec<EarningsResult>,
+
+   /// The next announcement, if one is scheduled
+   pub calendar: Option<EarningsCalendar>,
+
+   /// Estimates for the current & next quarters and fiscal years
+   pub estimates: Vec<EarningsEstimate>
+}
+impl Earnings {
+   fn new(data: yahoo::QuoteSummaryData) -> Earnings {
+      Earnings {
+         history: data.earnings_history.map_or_else(Vec::new, |history| history.history.into_iter().map(EarningsResult::new).collect()),
+         calendar: data.calendar_events.and_then(|events| events.earnings).map(EarningsCalendar::new),
+         estimates: data.earnings_trend.map_or_else(Vec::new, |trend| trend.trend.into_iter().filter_map(EarningsEstimate::new).collect())
+      }
+   }
+}
+
+/// Retrieves past earnings results, the next earnings announcement and analyst
+/// estimates for a symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::earnings;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = earnings::retrieve("AAPL").await.unwrap();
+///
+///    for result in &data.history {
+///       println!("{:?}: {:?} vs {:?} expected", result.quarter, result.eps_actual, result.eps_estimate);
+///    }
+///    if let Some(calendar) = data.calendar {
+///       println!("Next earnings expected {:?}", calendar.dates);
+///    }
+/// }
+/// ```
+pub async fn retrieve(symbol: &str) -> Result<Earnings> {
+   YahooClient::shared()?.earnings(symbol).await
+}
+
+impl YahooClient {
+   /// Retrieves past and upcoming earnings for a symbol - see [`earnings::retrieve`](earnings/fn.retrieve.html).
+   pub async fn earnings(&self, symbol: &str) -> Result<Earnings> {
+      Ok(Earnings::new(yahoo::load_summary(self, symbol, MODULES).await?))
+   }
+}
@@ -1,4 +1,5 @@
 use crate::Interval;
+use crate::history::Granularity;
 use reqwest;
 use snafu::Snafu;
 
@@ -12,27 +13,66 @@ pub enum InnerError {
    #[snafu(display("Yahoo! call failed. '{}' returned a {} result.", url, status))]
    CallFailed { url: String, status: u16 },
 
+   #[snafu(display("Failed to create the HTTP client - {}", source.to_string()))]
+   ClientFailed { source: reqwest::Error },
+
    #[snafu(display("Yahoo! chart failed to load {} - {}.", code, description))]
    ChartFailed { code: String, description: String },
 
+   #[snafu(display("Unable to decode a streamed quote - {}", source.to_string()))]
+   DecodeFailed { source: base64::DecodeError },
+
    #[snafu(display("An internal error occurred - please report that '{}'", reason))]
    InternalLogic { reason: String },
 
    #[snafu(display("An internal error occurred - please report that '{}' cannot be parsed because {}", url, source.to_string()))]
    InternalURL { url: String, source: url::ParseError },
 
+   #[snafu(display("Invalid value for the {} header", name))]
+   InvalidHeader { name: String, source: reqwest::header::InvalidHeaderValue },
+
+   #[snafu(display("The rate limit must be more than 0 calls per second, not {}", per_second))]
+   InvalidRateLimit { per_second: f64 },
+
    #[snafu(display("Start date cannot be after the end date"))]
    InvalidStartDate,
 
+   #[snafu(display("'{}' is not a valid URL - {}", url, source.to_string()))]
+   InvalidURL { url: String, source: url::ParseError },
+
+   #[snafu(display("Yahoo! only provides {} bars for the last {} days", granularity, days))]
+   LookbackExceeded { granularity: Granularity, days: i64 },
+
    #[snafu(display("Yahoo! returned invalid data - {}", reason))]
    MissingData { reason: String },
 
    #[snafu(display("Intraday intervals like {} are not allowed", interval))]
    NoIntraday { interval: Interval },
 
+   #[snafu(display("{} bars are not intraday bars", granularity))]
+   NotIntraday { granularity: Granularity },
+
+   #[snafu(display("Yahoo! options failed to load {} - {}.", code, description))]
+   OptionsFailed { code: String, description: String },
+
+   #[snafu(display("Yahoo! streamed a quote that cannot be read - {}", source.to_string()))]
+   ProtocolFailed { source: protobuf::ProtobufError },
+
+   #[snafu(display("Yahoo! quotes failed to load {} - {}.", code, description))]
+   QuoteFailed { code: String, description: String },
+
    #[snafu(display("Yahoo! call failed for unknown reason."))]
    RequestFailed { source: reqwest::Error },
 
+   #[snafu(display("Unable to start a Yahoo! session - {}", reason))]
+   SessionFailed { reason: String },
+
+   #[snafu(display("The quote stream failed - {}", source.to_string()))]
+   StreamFailed { source: tokio_tungstenite::tungstenite::Error },
+
+   #[snafu(display("Yahoo! quote summary failed to load {} - {}.", code, description))]
+   SummaryFailed { code: String, description: String },
+
    #[snafu(display("Unexpected Yahoo! failure. '{}' returned a {}", url, code))]
    UnexectedFailure { url: String, code: u16 },
 
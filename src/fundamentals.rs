@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+
+use crate::yahoo::{self, raw_date, raw_number};
+use crate::{Result, YahooClient};
+
+/// The quote summary modules the statements come from
+const MODULES: &[&str] = &[
+   "incomeStatementHistory", "incomeStatementHistoryQuarterly", "balanceSheetHistory", "balanceSheetHistoryQuarterly",
+   "cashflowStatementHistory", "cashflowStatementHistoryQuarterly"
+];
+
+/// Revenue, expenses and earnings over a fiscal year or quarter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IncomeStatement {
+   /// The last day of the period the statement covers
+   pub end_date: Option<DateTime<Utc>>,
+
+   pub total_revenue: Option<f64>,
+   pub cost_of_revenue: Option<f64>,
+   pub gross_profit: Option<f64>,
+   pub research_development: Option<f64>,
+   pub selling_general_administrative: Option<f64>,
+   pub total_operating_expenses: Option<f64>,
+   pub operating_income: Option<f64>,
+   pub ebit: Option<f64>,
+   pub interest_expense: Option<f64>,
+   pub income_before_tax: Option<f64>,
+   pub income_tax_expense: Option<f64>,
+   pub net_income: Option<f64>,
+   pub net_income_to_common: Option<f64>
+}
+impl IncomeStatement {
+   fn new(data: yahoo::IncomeStatementData) -> IncomeStatement {
+      IncomeStatement {
+         end_date: raw_date(data.end_date),
+         total_revenue: raw_number(data.total_revenue),
+         cost_of_revenue: raw_number(data.cost_of_revenue),
+         gross_profit: raw_number(data.gross_profit),
+         research_development: raw_number(data.research_development),
+         selling_general_administrative: raw_number(data.selling_general_administrative),
+         total_operating_expenses: raw_number(data.total_operating_expenses),
+         operating_income: raw_number(data.operating_income),
+         ebit: raw_number(data.ebit),
+         interest_expense: raw_number(data.interest_expense),
+         income_before_tax: raw_number(data.income_before_tax),
+         income_tax_expense: raw_number(data.income_tax_expense),
+         net_income: raw_number(data.net_income),
+         net_income_to_common: raw_number(data.net_income_applicable_to_common_shares)
+      }
+   }
+}
+
+/// Assets, liabilities and equity at the end of a fiscal year or quarter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BalanceSheet {
+   /// The day the balance sheet was drawn up
+   pub end_date: Option<DateTime<Utc>>,
+
+   pub cash: Option<f64>,
+   pub short_term_investments: Option<f64>,
+   pub net_receivables: Option<f64>,
+   pub inventory: Option<f64>,
+   pub total_current_assets: Option<f64>,
+   pub long_term_investments: Option<f64>,
+   pub property_plant_equipment: Option<f64>,
+   pub goodwill: Option<f64>,
+   pub intangible_assets: Option<f64>,
+   pub total_assets: Option<f64>,
+
+   pub accounts_payable: Option<f64>,
+   pub short_term_debt: Option<f64>,
+   pub total_current_liabilities: Option<f64>,
+   pub long_term_debt: Option<f64>,
+   pub total_liabilities: Option<f64>,
+
+   pub common_stock: Option<f64>,
+   pub retained_earnings: Option<f64>,
+   pub total_stockholder_equity: Option<f64>
+}
+impl BalanceSheet {
+   fn new(data: yahoo::BalanceSheetData) -> BalanceSheet {
+      BalanceSheet {
+         end_date: raw_date(data.end_date),
+         cash: raw_number(data.cash),
+         short_term_investments: raw_number(data.short_term_investments),
+         net_receivables: raw_number(data.net_receivables),
+         inventory: raw_number(data.inventory),
+         total_current_assets: raw_number(data.total_current_assets),
+         long_term_investments: raw_number(data.long_term_investments),
+         property_plant_equipment: raw_number(data.property_plant_equipment),
+         goodwill: raw_number(data.goodwill),
+         intangible_assets: raw_number(data.intangible_assets),
+         total_assets: raw_number(data.total_assets),
+         accounts_payable: raw_number(data.accounts_payable),
+         short_term_debt: raw_number(data.short_long_term_debt),
+         total_current_liabilities: raw_number(data.total_current_liabilities),
+         long_term_debt: raw_number(data.long_term_debt),
+         total_liabilities: raw_number(data.total_liabilities),
+         common_stock: raw_number(data.common_stock),
+         retained_earnings: raw_number(data.retained_earnings),
+         total_stockholder_equity: raw_number(data.total_stockholder_equity)
+      }
+   }
+}
+
+/// Where cash came from and went to over a fiscal year or quarter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CashFlow {
+   /// The last day of the period the statement covers
+   pub end_date: Option<DateTime<Utc>>,
+
+   pub net_income: Option<f64>,
+   pub depreciation: Option<f64>,
+   pub change_to_receivables: Option<f64>,
+   pub change_to_inventory: Option<f64>,
+   pub operating_cash_flow: Option<f64>,
+
+   pub capital_expenditures: Option<f64>,
+   pub investments: Option<f64>,
+   pub investing_cash_flow: Option<f64>,
+
+   pub dividends_paid: Option<f64>,
+   pub net_borrowings: Option<f64>,
+   pub repurchase_of_stock: Option<f64>,
+   pub issuance_of_stock: Option<f64>,
+   pub financing_cash_flow: Option<f64>,
+
+   pub change_in_cash: Option<f64>
+}
+impl CashFlow {
+   fn new(data: yahoo::CashFlowData) -> CashFlow {
+      CashFlow {
+         end_date: raw_date(data.end_date),
+         net_income: raw_number(data.net_income),
+         depreciation: raw_number(data.depreciation),
+         change_to_receivables: raw_number(data.change_to_account_receivables),
+         change_to_inventory: raw_number(data.change_to_inventory),
+         operating_cash_flow: raw_number(data.total_cash_from_operating_activities),
+         capital_expenditures: raw_number(data.capital_expenditures),
+         investments: raw_number(data.investments),
+         investing_cash_flow: raw_number(data.total_cashflows_from_investing_activities),
+         dividends_paid: raw_number(data.dividends_paid),
+         net_borrowings: raw_number(data.net_borrowings),
+         repurchase_of_stock: raw_number(data.repurchase_of_stock),
+         issuance_of_stock: raw_number(data.issuance_of_stock),
+         financing_cash_flow: raw_number(data.total_cash_from_financing_activities),
+         change_in_cash: raw_number(data.change_in_cash)
+      }
+   }
+}
+
+/// The financial statements for a run of fiscal years or quarters - most recent
+/// first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statements {
+   pub income_statements: Vec<IncomeStatement>,
+   pub balance_sheets: Vec<BalanceSheet>,
+   pub cash_flows: Vec<CashFlow>
+}
+
+/// The annual and quarterly financial statements for a symbol.  Symbols
+/// without financial statements (ie. funds) have none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fundamentals {
+   pub annual: Statements,
+   pub quarterly: Statements
+}
+impl Fundamentals {
+   fn new(data: yahoo::QuoteSummaryData) -> Fundamentals {
+      Fundamentals {
+         annual: Statements {
+            income_statements: data.income_statement_history
+               .map_or_else(Vec::new, |history| history.income_statement_history.into_iter().map(IncomeStatement::new).collect()),
+            balance_sheets: data.balance_sheet_history
+               .map_or_else(Vec::new, |history| history.balance_sheet_statements.into_iter().map(BalanceSheet::new).collect()),
+            cash_flows: data.cash_flow_history
+               .map_or_else(Vec::new, |history| history.cashflow_statements.into_iter().map(CashFlow::new).collect())
+         },
+         quarterly: Statements {
+            income_statements: data.income_statement_history_quarterly
+               .map_or_else(Vec::new, |history| history.income_statement_history.into_iter().map(IncomeStatement::new).collect()),
+            balance_sheets: data.balance_sheet_history_quarterly
+               .map_or_else(Vec::new, |history| history.balance_sheet_statements.into_iter().map(BalanceSheet::new).collect()),
+            cash_flows: data.cash_flow_history_quarterly
+               .map_or_else(Vec::new, |history| history.cashflow_statements.into_iter().map(CashFlow::new).collect())
+         }
+      }
+   }
+}
+
+/// Retrieves the annual and quarterly income statements, balance sheets and
+/// cash flows for a symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::fundamentals;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = fundamentals::retrieve("AAPL").await.unwrap();
+///
+///    for statement in &data.annual.income_statements {
+///       println!("{:?}: revenue {:?}, net income {:?}", statement.end_date, statement.total_revenue, statement.net_income);
+///    }
+/// }
+/// ```
+pub async fn retrieve(symbol: &str) -> Result<Fundamentals> {
+   YahooClient::shared()?.fundamentals(symbol).await
+}
+
+impl YahooClient {
+   /// Retrieves the financial statements for a symbol - see [`fundamentals::retrieve`](fundamentals/fn.retrieve.html).
+   pub async fn fundamentals(&self, symbol: &str) -> Result<Fundamentals> {
+      Ok(Fundamentals::new(yahoo::load_summary(self, symbol, MODULES).await?))
+   }
+}
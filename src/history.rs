@@ -1,9 +1,320 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use futures::stream::{self, StreamExt};
 use snafu::{ensure, OptionExt};
+use std::collections::HashMap;
+use std::fmt;
 
-use crate::{error, yahoo, Bar, Interval, Result};
+use crate::{error, yahoo, Bar, Interval, Result, YahooClient};
+
+/// The size of each bar returned by Yahoo!
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Granularity { _1m, _2m, _5m, _15m, _30m, _60m, _90m, _1d, _5d, _1wk, _1mo, _3mo }
+impl Granularity {
+   /// Whether the bars are smaller than a trading day
+   pub fn is_intraday(self) -> bool { self.lookback().is_some() }
+
+   /// The furthest back (in days) Yahoo! will serve bars of this size.  `None`
+   /// means there is no limit.
+   pub fn lookback(self) -> Option<i64> {
+      match self {
+         Granularity::_1m => Some(7),
+         Granularity::_2m | Granularity::_5m | Granularity::_15m | Granularity::_30m | Granularity::_90m => Some(60),
+         Granularity::_60m => Some(730),
+         _ => None
+      }
+   }
+}
+impl fmt::Display for Granularity {
+   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      let value = match self {
+         Granularity::_1m => "1m",
+         Granularity::_2m => "2m",
+         Granularity::_5m => "5m",
+         Granularity::_15m => "15m",
+         Granularity::_30m => "30m",
+         Granularity::_60m => "60m",
+         Granularity::_90m => "90m",
+         Granularity::_1d => "1d",
+         Granularity::_5d => "5d",
+         Granularity::_1wk => "1wk",
+         Granularity::_1mo => "1mo",
+         Granularity::_3mo => "3mo"
+      };
+      write!(f, "{}", value)
+   }
+}
+
+/// The (worst case) number of calendar days covered by a range.  `None` means
+/// the range has no upper bound.
+fn range_days(range: &Interval) -> Option<i64> {
+   match range {
+      Interval::_1d => Some(1),
+      Interval::_5d => Some(5),
+      Interval::_1mo => Some(31),
+      Interval::_3mo => Some(92),
+      Interval::_6mo => Some(184),
+      Interval::_1y => Some(366),
+      Interval::_2y => Some(731),
+      Interval::_5y => Some(1827),
+      Interval::_10y => Some(3653),
+      Interval::_ytd => Some(i64::from(Utc::now().ordinal())),
+      _ => None
+   }
+}
+
+/// Historical OHLCV data along with the close adjusted for splits & dividends.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdjustedBar {
+   /// Milliseconds since the epoch
+   pub timestamp: i64,
+
+   pub open: f64,
+   pub high: f64,
+   pub low: f64,
+
+   /// The close as traded on the day
+   pub close: f64,
+
+   /// The close adjusted for every split & dividend after the day
+   pub adjusted_close: f64,
+
+   pub volume: Option<u64>
+}
+impl AdjustedBar {
+   /// The bar as traded, without any adjustments
+   pub fn bar(&self) -> Bar {
+      Bar { timestamp: self.timestamp, open: self.open, high: self.high, low: self.low, close: self.close, volume: self.volume }
+   }
+
+   /// The bar with the open, high & low adjusted by the same ratio as the close.
+   pub fn back_adjusted(&self) -> Bar {
+      let ratio = if self.close == 0.0 { 1.0 } else { self.adjusted_close / self.close };
+
+      Bar {
+         timestamp: self.timestamp,
+         open: self.open * ratio,
+         high: self.high * ratio,
+         low: self.low * ratio,
+         close: self.adjusted_close,
+         volume: self.volume
+      }
+   }
+}
+
+/// A cash dividend paid out to shareholders.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dividend {
+   /// The first day the symbol trades without the dividend
+   pub ex_date: DateTime<Utc>,
+
+   /// The cash paid out per share
+   pub amount: f64
+}
+
+/// A stock split - ie. a 4:1 split has a numerator of 4 and a denominator of 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Split {
+   pub date: DateTime<Utc>,
+   pub numerator: f64,
+   pub denominator: f64
+}
+
+/// The corporate actions for a symbol, ordered by date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Events {
+   pub dividends: Vec<Dividend>,
+   pub splits: Vec<Split>
+}
+
+/// The hours of a trading session on the current trading day
+#[derive(Debug, Clone, PartialEq)]
+pub struct TradingPeriod {
+   /// The short name of the exchange timezone - ie. 'EDT'
+   pub timezone: String,
+
+   pub start: DateTime<Utc>,
+   pub end: DateTime<Utc>,
+
+   /// Seconds offset from UTC during the session
+   pub gmt_offset: i32
+}
+impl TradingPeriod {
+   fn new(data: &yahoo::TradingPeriod) -> TradingPeriod {
+      TradingPeriod { timezone: data.timezone.clone(), start: data.start, end: data.end, gmt_offset: data.gmtoffset }
+   }
+}
+
+/// Information about the symbol a chart was loaded for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartMeta {
+   pub symbol: String,
+
+   /// The currency prices are quoted in - ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The exchange code, according to Yahoo.  ie. 'NMS'
+   pub exchange_name: Option<String>,
+
+   /// The kind of security - ie. 'EQUITY' or 'ETF'
+   pub instrument_type: Option<String>,
+
+   pub first_trade_date: Option<DateTime<Utc>>,
+   pub current_price: f64,
+   pub previous_close: Option<f64>,
+
+   /// Current offset of the exchange from UTC in seconds
+   pub gmt_offset: Option<i32>,
+
+   /// The IANA name of the exchange timezone - ie. 'America/New_York'
+   pub exchange_timezone_name: Option<String>,
+
+   /// The number of decimal places prices are usually shown with
+   pub price_hint: Option<u32>,
+
+   /// The pre-market, regular & after hours sessions of the current trading day
+   pub pre_market: Option<TradingPeriod>,
+   pub regular_market: Option<TradingPeriod>,
+   pub post_market: Option<TradingPeriod>,
+
+   /// The ranges Yahoo! will accept for the symbol - ie. '5d', 'ytd'
+   pub valid_ranges: Vec<String>
+}
+impl ChartMeta {
+   fn new(data: &yahoo::Meta) -> ChartMeta {
+      let periods = data.current_trading_period.as_ref();
+
+      ChartMeta {
+         symbol: data.symbol.clone(),
+         currency: data.currency.clone(),
+         exchange_name: data.exchange_name.clone(),
+         instrument_type: data.instrument_type.clone(),
+         first_trade_date: data.first_trade_date,
+         current_price: data.current_price,
+         previous_close: data.previous_close,
+         gmt_offset: data.gmtoffset,
+         exchange_timezone_name: data.exchange_timezone_name.clone(),
+         price_hint: data.price_hint,
+         pre_market: periods.map(|periods| TradingPeriod::new(&periods.pre)),
+         regular_market: periods.map(|periods| TradingPeriod::new(&periods.regular)),
+         post_market: periods.map(|periods| TradingPeriod::new(&periods.post)),
+         valid_ranges: data.valid_ranges.clone()
+      }
+   }
+
+   /// The timezone of the exchange - `None` if Yahoo! left it out or the name
+   /// is not one we know of.
+   pub fn timezone(&self) -> Option<Tz> { self.exchange_timezone_name.as_ref()?.parse().ok() }
+
+   /// Converts a timestamp (in milliseconds) to the local time of the exchange.
+   ///
+   /// Daylight savings are taken into account when the exchange timezone is
+   /// known, otherwise the current offset of the exchange is used - `None` if
+   /// Yahoo! sent neither (or an offset that is out of range).
+   pub fn local_datetime(&self, timestamp: i64) -> Option<DateTime<FixedOffset>> {
+      let datetime = Utc.timestamp_millis(timestamp);
+      let offset = match self.timezone() {
+         Some(tz) => datetime.with_timezone(&tz).offset().fix(),
+         None => FixedOffset::east_opt(self.gmt_offset?)?
+      };
+      Some(datetime.with_timezone(&offset))
+   }
+}
+
+/// OHLCV data along with information about what was loaded.
+#[derive(Debug, Clone)]
+pub struct Chart {
+   pub meta: ChartMeta,
+   pub bars: Vec<Bar>
+}
+impl Chart {
+   /// The bars stamped in the local time of the exchange - `None` if the
+   /// exchange's timezone isn't known (see `ChartMeta::local_datetime`).
+   pub fn local_bars(&self) -> Option<Vec<LocalBar>> {
+      self.bars.iter()
+         .map(|bar| Some(LocalBar { datetime: self.meta.local_datetime(bar.timestamp)?, bar: bar.clone() }))
+         .collect()
+   }
+}
+
+/// A bar stamped in the local time of the exchange it traded on.
+#[derive(Debug, Clone)]
+pub struct LocalBar {
+   pub datetime: DateTime<FixedOffset>,
+   pub bar: Bar
+}
+impl LocalBar {
+   /// The calendar date the bar traded on, as seen by the exchange
+   pub fn trading_date(&self) -> NaiveDate { self.datetime.naive_local().date() }
+}
+
+/// Makes sure Yahoo! can serve bars of the given size between the dates,
+/// returning the end date to use.
+fn check_range(start: DateTime<Utc>, end: Option<DateTime<Utc>>, granularity: Granularity) -> Result<DateTime<Utc>> {
+   let now = Utc::now();
+   let _end = end.unwrap_or(now);
+   ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+   if let Some(days) = granularity.lookback() {
+      ensure!(now.signed_duration_since(start).num_days() < days, error::LookbackExceeded { granularity, days });
+   }
+   Ok(_end)
+}
+
+fn aggregate_chart(data: yahoo::Data) -> Result<Chart> {
+   let meta = ChartMeta::new(&data.meta);
+   Ok(Chart { meta, bars: aggregate_bars(data)? })
+}
+
+fn aggregate_events(data: yahoo::Data) -> Events {
+   let events = match data.events {
+      Some(events) => events,
+      None => return Events { dividends: Vec::new(), splits: Vec::new() }
+   };
+
+   let mut dividends = events.dividends.values()
+      .map(|dividend| Dividend { ex_date: Utc.timestamp(dividend.date, 0), amount: dividend.amount })
+      .collect::<Vec<_>>();
+   dividends.sort_by_key(|dividend| dividend.ex_date);
+
+   let mut splits = events.splits.values()
+      .map(|split| Split { date: Utc.timestamp(split.date, 0), numerator: split.numerator, denominator: split.denominator })
+      .collect::<Vec<_>>();
+   splits.sort_by_key(|split| split.date);
+
+   Events { dividends, splits }
+}
 
 fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
+   Ok(index_bars(&data)?.into_iter().map(|(_, bar)| bar).collect())
+}
+
+fn aggregate_adjusted(data: yahoo::Data) -> Result<Vec<AdjustedBar>> {
+   let bars = index_bars(&data)?;
+   if bars.is_empty() { return Ok(Vec::new()); }
+
+   // make sure the adjusted closes line up with the OHLCV data
+   let adjcloses = &data.indicators.adjcloses;
+   ensure!(!adjcloses.is_empty(), error::MissingData { reason: "no adjusted close data" });
+   let adjclose = &adjcloses[0].adjcloses;
+   ensure!(data.timestamps.len() == adjclose.len(), error::MissingData { reason: "'adjclose' values do not line up the timestamps" });
+
+   Ok(bars.into_iter()
+      // skip days where we have no adjusted close
+      .filter_map(|(i, bar)| adjclose[i].map(|adjusted_close| AdjustedBar {
+         timestamp: bar.timestamp,
+         open: bar.open,
+         high: bar.high,
+         low: bar.low,
+         close: bar.close,
+         adjusted_close,
+         volume: bar.volume
+      }))
+      .collect())
+}
+
+/// Builds up the complete bars, along with the index of the data they came from
+fn index_bars(data: &yahoo::Data) -> Result<Vec<(usize, Bar)>> {
    let mut result = Vec::new();
 
    let timestamps = &data.timestamps;
@@ -31,14 +342,14 @@ fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
          continue;
       }
 
-      result.push(Bar {
+      result.push((i, Bar {
          timestamp: timestamps[i] * 1000,
          open: quote.opens[i].context(error::InternalLogic{ reason: "missing open not caught" })?,
          high: quote.highs[i].context(error::InternalLogic{ reason: "missing high not caught" })?,
          low: quote.lows[i].context(error::InternalLogic{ reason: "missing low not caught" })?,
          close: quote.closes[i].context(error::InternalLogic{ reason: "missing close not caught" })?,
          volume: quote.volumes[i],
-      })
+      }))
    }
    Ok(result)
 }
@@ -65,7 +376,7 @@ fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
 /// }
 /// ```
 pub async fn retrieve(symbol: &str) -> Result<Vec<Bar>> {
-   aggregate_bars(yahoo::load_daily(symbol, Interval::_6mo).await?)
+   YahooClient::shared()?.history(symbol).await
 }
 
 /// Retrieves a configurable amount of OCLHV data for a symbol
@@ -92,10 +403,7 @@ pub async fn retrieve(symbol: &str) -> Result<Vec<Bar>> {
 /// }
 /// ```
 pub async fn retrieve_interval(symbol: &str, interval: Interval) -> Result<Vec<Bar>> {
-   // pre-conditions
-   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
-
-   aggregate_bars(yahoo::load_daily(symbol, interval).await?)
+   YahooClient::shared()?.history_interval(symbol, interval).await
 }
 
 /// Retrieves OCLHV data for a symbol between a start and end date.
@@ -121,9 +429,299 @@ pub async fn retrieve_interval(symbol: &str, interval: Interval) -> Result<Vec<B
 /// }
 /// ```
 pub async fn retrieve_range(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
-   // pre-conditions
-   let _end = end.unwrap_or_else(Utc::now);
-   ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+   YahooClient::shared()?.history_range(symbol, start, end).await
+}
 
-   aggregate_bars(yahoo::load_daily_range(symbol, start.timestamp(), _end.timestamp()).await?)
+/// Retrieves intraday OCLHV data for a symbol, with bars of the given size,
+/// covering the range up to now.
+///
+/// Yahoo! only keeps intraday data for a limited time so the range must fit
+/// within the lookback of the granularity - 7 days for 1 minute bars, 730 days
+/// for 60 minute bars and 60 days for everything else.
+///
+/// # Examples
+///
+/// Get the last day of 5 minute bars for Apple:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, history::Granularity, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_intraday("AAPL", Granularity::_5m, Interval::_1d).await {
+///       Err(e) => println!("Failed to call Yahoo {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("At {} Apple traded at ${:.2}", bar.datetime().format("%H:%M"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_intraday(symbol: &str, granularity: Granularity, range: Interval) -> Result<Vec<Bar>> {
+   YahooClient::shared()?.history_intraday(symbol, granularity, range).await
+}
+
+/// Retrieves OCLHV data for a symbol between a start and end date, with bars
+/// of the given size.
+///
+/// Intraday bars are only available for a limited time so the start date
+/// must fall within the lookback of the granularity.
+///
+/// # Examples
+///
+/// Get a year of weekly bars for Apple:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, history::Granularity, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let now = Utc::now();
+///    match history::retrieve_range_granularity("AAPL", now - Duration::days(365), None, Granularity::_1wk).await {
+///       Err(e) => println!("Failed to call Yahoo {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("The week of {} Apple closed at ${:.2}", bar.datetime().format("%b %e %Y"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_range_granularity(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>, granularity: Granularity) -> Result<Vec<Bar>> {
+   YahooClient::shared()?.history_range_granularity(symbol, start, end, granularity).await
+}
+
+/// Retrieves a configurable amount of daily OCLHV data for a symbol, along
+/// with the close adjusted for splits & dividends.
+///
+/// # Examples
+///
+/// Get the total return of Apple for the last year:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = history::retrieve_adjusted("AAPL", Interval::_1y).await.unwrap();
+///    if let (Some(first), Some(last)) = (data.first(), data.last()) {
+///       println!("Apple returned {:.2}%", (last.adjusted_close / first.adjusted_close - 1.0) * 100.0)
+///    }
+/// }
+/// ```
+pub async fn retrieve_adjusted(symbol: &str, interval: Interval) -> Result<Vec<AdjustedBar>> {
+   YahooClient::shared()?.history_adjusted(symbol, interval).await
+}
+
+/// Retrieves daily OCLHV data for a symbol between a start and end date, along
+/// with the close adjusted for splits & dividends.
+///
+/// # Examples
+///
+/// Get back-adjusted bars for Apple over the last month:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = history::retrieve_adjusted_range("AAPL", Utc::now() - Duration::days(30), None).await.unwrap();
+///    for bar in data.iter().map(|bar| bar.back_adjusted()) {
+///       println!("On {} Apple closed at an adjusted ${:.2}", bar.datetime().format("%b %e %Y"), bar.close)
+///    }
+/// }
+/// ```
+pub async fn retrieve_adjusted_range(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<AdjustedBar>> {
+   YahooClient::shared()?.history_adjusted_range(symbol, start, end).await
+}
+
+/// Retrieves the dividends & splits for a symbol between a start and end date.
+///
+/// # Examples
+///
+/// Get the last 5 years of Apple dividends:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let events = history::retrieve_events("AAPL", Utc::now() - Duration::days(5 * 365), None).await.unwrap();
+///    for dividend in &events.dividends {
+///       println!("On {} Apple paid ${:.4}", dividend.ex_date.format("%b %e %Y"), dividend.amount)
+///    }
+/// }
+/// ```
+pub async fn retrieve_events(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Events> {
+   YahooClient::shared()?.history_events(symbol, start, end).await
+}
+
+/// Retrieves a configurable amount of daily OCLHV data for a symbol along with
+/// the chart metadata - ie. the currency & exchange.
+///
+/// # Examples
+///
+/// Get the currency of the last 5 days of Toyota data:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let chart = history::retrieve_chart("7203.T", Interval::_5d).await.unwrap();
+///    for bar in &chart.bars {
+///       println!("Toyota closed at {:.2} {}", bar.close, chart.meta.currency.as_deref().unwrap_or("?"))
+///    }
+/// }
+/// ```
+pub async fn retrieve_chart(symbol: &str, interval: Interval) -> Result<Chart> {
+   YahooClient::shared()?.history_chart(symbol, interval).await
+}
+
+/// Retrieves OCLHV data for a symbol between a start and end date, with bars
+/// of the given size, along with the chart metadata - ie. the currency & exchange.
+///
+/// # Examples
+///
+/// Get the exchange timezone along with a month of weekly Apple data:
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::{ history, history::Granularity };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let chart = history::retrieve_chart_range("AAPL", Utc::now() - Duration::days(30), None, Granularity::_1wk).await.unwrap();
+///    println!("{} bars in {}", chart.bars.len(), chart.meta.exchange_timezone_name.unwrap_or_default())
+/// }
+/// ```
+pub async fn retrieve_chart_range(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>, granularity: Granularity) -> Result<Chart> {
+   YahooClient::shared()?.history_chart_range(symbol, start, end, granularity).await
+}
+
+/// Retrieves a configurable amount of OCLHV data for many symbols at once,
+/// ending on the last market close.
+///
+/// The calls are made concurrently (up to the concurrency of the client) and
+/// each symbol gets its own result so that one bad symbol doesn't fail the rest.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = history::retrieve_many(&["AAPL", "MSFT", "FUBAR"], Interval::_1mo).await.unwrap();
+///    for (symbol, result) in &data {
+///       match result {
+///          Err(e) => println!("Failed to load {}: {:?}", symbol, e),
+///          Ok(bars) => println!("Loaded {} days of {}", bars.len(), symbol)
+///       }
+///    }
+/// }
+/// ```
+pub async fn retrieve_many(symbols: &[&str], interval: Interval) -> Result<HashMap<String, Result<Vec<Bar>>>> {
+   YahooClient::shared()?.history_many(symbols, interval).await
+}
+
+impl YahooClient {
+   /// Retrieves (at most) 6 months worth of OCLHV data for a symbol - see [`history::retrieve`](history/fn.retrieve.html).
+   pub async fn history(&self, symbol: &str) -> Result<Vec<Bar>> {
+      aggregate_bars(yahoo::load_daily(self, symbol, &Interval::_6mo).await?)
+   }
+
+   /// Retrieves a configurable amount of OCLHV data for a symbol - see [`history::retrieve_interval`](history/fn.retrieve_interval.html).
+   pub async fn history_interval(&self, symbol: &str, interval: Interval) -> Result<Vec<Bar>> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      aggregate_bars(yahoo::load_daily(self, symbol, &interval).await?)
+   }
+
+   /// Retrieves OCLHV data for a symbol between a start and end date - see [`history::retrieve_range`](history/fn.retrieve_range.html).
+   pub async fn history_range(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      aggregate_bars(yahoo::load_daily_range(self, symbol, start.timestamp(), _end.timestamp()).await?)
+   }
+
+   /// Retrieves intraday OCLHV data for a symbol - see [`history::retrieve_intraday`](history/fn.retrieve_intraday.html).
+   pub async fn history_intraday(&self, symbol: &str, granularity: Granularity, range: Interval) -> Result<Vec<Bar>> {
+      // pre-conditions
+      ensure!(!range.is_intraday(), error::NoIntraday { interval: range });
+      let days = granularity.lookback().context(error::NotIntraday { granularity })?;
+      ensure!(matches!(range_days(&range), Some(covered) if covered <= days), error::LookbackExceeded { granularity, days });
+
+      aggregate_bars(yahoo::load_intraday(self, symbol, range, &granularity.to_string()).await?)
+   }
+
+   /// Retrieves OCLHV data for a symbol between a start and end date, with bars of the given size - see [`history::retrieve_range_granularity`](history/fn.retrieve_range_granularity.html).
+   pub async fn history_range_granularity(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>, granularity: Granularity) -> Result<Vec<Bar>> {
+      // pre-conditions
+      let _end = check_range(start, end, granularity)?;
+
+      aggregate_bars(yahoo::load_range(self, symbol, start.timestamp(), _end.timestamp(), &granularity.to_string()).await?)
+   }
+
+   /// Retrieves daily OCLHV data for a symbol along with the adjusted close - see [`history::retrieve_adjusted`](history/fn.retrieve_adjusted.html).
+   pub async fn history_adjusted(&self, symbol: &str, interval: Interval) -> Result<Vec<AdjustedBar>> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      aggregate_adjusted(yahoo::load_daily(self, symbol, &interval).await?)
+   }
+
+   /// Retrieves daily OCLHV data for a symbol between a start and end date along with the adjusted close - see [`history::retrieve_adjusted_range`](history/fn.retrieve_adjusted_range.html).
+   pub async fn history_adjusted_range(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<AdjustedBar>> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      aggregate_adjusted(yahoo::load_daily_range(self, symbol, start.timestamp(), _end.timestamp()).await?)
+   }
+
+   /// Retrieves the dividends & splits for a symbol between a start and end date - see [`history::retrieve_events`](history/fn.retrieve_events.html).
+   pub async fn history_events(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Events> {
+      // pre-conditions
+      let _end = end.unwrap_or_else(Utc::now);
+      ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      Ok(aggregate_events(yahoo::load_events(self, symbol, start.timestamp(), _end.timestamp()).await?))
+   }
+
+   /// Retrieves daily OCLHV data for a symbol along with the chart metadata - see [`history::retrieve_chart`](history/fn.retrieve_chart.html).
+   pub async fn history_chart(&self, symbol: &str, interval: Interval) -> Result<Chart> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      aggregate_chart(yahoo::load_daily(self, symbol, &interval).await?)
+   }
+
+   /// Retrieves OCLHV data for a symbol between a start and end date along with the chart metadata - see [`history::retrieve_chart_range`](history/fn.retrieve_chart_range.html).
+   pub async fn history_chart_range(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>, granularity: Granularity) -> Result<Chart> {
+      // pre-conditions
+      let _end = check_range(start, end, granularity)?;
+
+      aggregate_chart(yahoo::load_range(self, symbol, start.timestamp(), _end.timestamp(), &granularity.to_string()).await?)
+   }
+
+   /// Retrieves OCLHV data for many symbols at once - see [`history::retrieve_many`](history/fn.retrieve_many.html).
+   pub async fn history_many(&self, symbols: &[&str], interval: Interval) -> Result<HashMap<String, Result<Vec<Bar>>>> {
+      // pre-conditions
+      ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+      let interval = &interval;
+      Ok(stream::iter(symbols)
+         .map(|symbol| async move {
+            (symbol.to_string(), yahoo::load_daily(self, symbol, interval).await.and_then(aggregate_bars))
+         })
+         .buffer_unordered(self.concurrency())
+         .collect()
+         .await)
+   }
 }
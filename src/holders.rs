@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+
+use crate::yahoo::{self, raw_date, raw_number};
+use crate::{Result, YahooClient};
+
+/// The quote summary modules the holders come from
+const MODULES: &[&str] = &["majorHoldersBreakdown", "institutionOwnership", "fundOwnership", "insiderTransactions", "insiderHolders"];
+
+/// How the ownership of a symbol breaks down.  Percentages are fractions - ie.
+/// 0.6 is 60%.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MajorHolders {
+   pub insiders_percent_held: Option<f64>,
+   pub institutions_percent_held: Option<f64>,
+
+   /// The percentage of the float (rather than all shares) held by institutions
+   pub institutions_float_percent_held: Option<f64>,
+
+   pub institutions_count: Option<u64>
+}
+impl MajorHolders {
+   fn new(data: yahoo::MajorHoldersBreakdown) -> MajorHolders {
+      MajorHolders {
+         insiders_percent_held: raw_number(data.insiders_percent_held),
+         institutions_percent_held: raw_number(data.institutions_percent_held),
+         institutions_float_percent_held: raw_number(data.institutions_float_percent_held),
+         institutions_count: raw_number(data.institutions_count).map(|count| count as u64)
+      }
+   }
+}
+
+/// An institution or mutual fund holding a position in a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holder {
+   /// The name of the holder - ie. 'Vanguard Group, Inc. (The)'
+   pub holder: String,
+
+   pub shares: Option<u64>,
+   pub value: Option<f64>,
+
+   /// The fraction of all shares held - ie. 0.07 is 7%
+   pub percent_held: Option<f64>,
+
+   /// When the holder last reported the position
+   pub report_date: Option<DateTime<Utc>>
+}
+impl Holder {
+   fn new(data: yahoo::OwnershipData) -> Option<Holder> {
+      Some(Holder {
+         holder: data.organization?,
+         shares: raw_number(data.position).map(|shares| shares as u64),
+         value: raw_number(data.value),
+         percent_held: raw_number(data.pct_held),
+         report_date: raw_date(data.report_date)
+      })
+   }
+}
+
+/// A purchase, sale, grant, etc. of shares by an insider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsiderTransaction {
+   pub insider: String,
+
+   /// The insider's relationship to the company - ie. 'Chief Executive Officer'
+   pub relation: Option<String>,
+
+   /// What happened, according to Yahoo.  ie. 'Sale at price 112.00 per share.'
+   pub description: Option<String>,
+
+   pub date: Option<DateTime<Utc>>,
+   pub shares: Option<u64>,
+   pub value: Option<f64>,
+
+   /// Whether the shares are held directly by the insider (rather than indirectly, ie. through a trust)
+   pub direct: Option<bool>
+}
+impl InsiderTransaction {
+   fn new(data: yahoo::InsiderTransactionData) -> Option<InsiderTransaction> {
+      Some(InsiderTransaction {
+         insider: data.filer_name?,
+         relation: data.filer_relation,
+         description: data.transaction_text.filter(|text| !text.is_empty()),
+         date: raw_date(data.start_date),
+         shares: raw_number(data.shares).map(|shares| shares as u64),
+         value: raw_number(data.value),
+         direct: data.ownership.map(|ownership| ownership == "D")
+      })
+   }
+}
+
+/// An insider on the roster of a company, with their latest transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Insider {
+   pub name: String,
+
+   /// The insider's relationship to the company - ie. 'Director'
+   pub relation: Option<String>,
+
+   pub latest_transaction: Option<String>,
+   pub latest_transaction_date: Option<DateTime<Utc>>,
+
+   /// The shares held directly, as of the position date
+   pub position_direct: Option<u64>,
+   pub position_date: Option<DateTime<Utc>>
+}
+impl Insider {
+   fn new(data: yahoo::InsiderHolderData) -> Option<Insider> {
+      Some(Insider {
+         name: data.name?,
+         relation: data.relation,
+         latest_transaction: data.transaction_description,
+         latest_transaction_date: raw_date(data.latest_trans_date),
+         position_direct: raw_number(data.position_direct).map(|shares| shares as u64),
+         position_date: raw_date(data.position_direct_date)
+      })
+   }
+}
+
+/// Who owns a symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holders {
+   pub major_holders: Option<MajorHolders>,
+
+   /// The largest institutional holders
+   pub institutions: Vec<Holder>,
+
+   /// The largest mutual fund holders
+   pub funds: Vec<Holder>,
+
+   /// Recent insider transactions, most recent first
+   pub insider_transactions: Vec<InsiderTransaction>,
+
+   pub insiders: Vec<Insider>
+}
+impl Holders {
+   fn new(data: yahoo::QuoteSummaryData) -> Holders {
+      Holders {
+         major_holders: data.major_holders_breakdown.map(MajorHolders::new),
+         institutions: data.institution_ownership.map_or_else(Vec::new, |ownership| ownership.ownership_list.into_iter().filter_map(Holder::new).collect()),
+         funds: data.fund_ownership.map_or_else(Vec::new, |ownership| ownership.ownership_list.into_iter().filter_map(Holder::new).collect()),
+         insider_transactions: data.insider_transactions
+            .map_or_else(Vec::new, |transactions| transactions.transactions.into_iter().filter_map(InsiderTransaction::new).collect()),
+         insiders: data.insider_holders.map_or_else(Vec::new, |holders| holders.holders.into_iter().filter_map(Insider::new).collect())
+      }
+   }
+}
+
+/// Retrieves the major holders, top institutional & mutual fund holders and
+/// insider activity for a symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::holders;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let data = holders::retrieve("AAPL").await.unwrap();
+///
+///    for holder in &data.institutions {
+///       println!("{} holds {:?} shares ({:?})", holder.holder, holder.shares, holder.percent_held);
+///    }
+/// }
+/// ```
+pub async fn retrieve(symbol: &str) -> Result<Holders> {
+   YahooClient::shared()?.holders(symbol).await
+}
+
+impl YahooClient {
+   /// Retrieves who owns a symbol - see [`holders::retrieve`](holders/fn.retrieve.html).
+   pub async fn holders(&self, symbol: &str) -> Result<Holders> {
+      Ok(Holders::new(yahoo::load_summary(self, symbol, MODULES).await?))
+   }
+}
@@ -6,7 +6,16 @@
 //! Currently `yahoo_finance` provides:
 //! * Historical quote information [OHCL Data](https://en.wikipedia.org/wiki/Open-high-low-close_chart) + volume
 //! * Relatively real-time quote informaton with comparible performance to the real-time updates on their website
+//! * Quote snapshots for many symbols at once, including bid / ask, ranges and valuation
+//! * Annual and quarterly financial statements - income, balance sheet and cash flow
+//! * Earnings history, the next earnings date and analyst estimates
+//! * Analyst recommendations, rating changes and price targets
+//! * Major, institutional & mutual fund holders along with insider transactions
+//! * Option chains - calls and puts for every expiration date
+//! * Key statistics and valuation measures - ie. market cap, PE, beta, short interest
 //! * Company profile information including address, sector, industry, etc.
+//! * Fund profiles including holdings, sector weightings, asset allocation and fees
+//! * A local mock of the streamer for testing streaming code offline - behind the `test-util` feature
 //! 
 //! ## Quick Examples
 //!
@@ -37,7 +46,8 @@
 //! async fn main() {
 //!    let streamer = Streamer::new(vec!["AAPL", "QQQ", "^DJI", "^IXIC"]);
 //!
-//!    streamer.stream().await
+//!    streamer.stream().await.unwrap()
+//!       .filter_map(|quote| future::ready(quote.ok()))
 //!       .for_each(|quote| {
 //!          println!("At {}, {} is trading for ${}", quote.timestamp, quote.symbol, quote.price);
 //!          future::ready(())
@@ -76,13 +86,45 @@ pub type Result<T> = std::result::Result<T, Error>;
 
 mod yahoo;
 
+/// Shared Yahoo! client
+mod client;
+mod session;
+mod throttle;
+pub use client::{YahooClient, YahooClientBuilder};
+pub use throttle::RetryPolicy;
+
 /// Historical quotes
 pub mod history;
 
+/// Analyst recommendations & price targets
+pub mod analysts;
+
+/// Earnings history & estimates
+pub mod earnings;
+
+/// Financial statements
+pub mod fundamentals;
+
+/// Institutional & insider holders
+pub mod holders;
+
+/// Option chains
+pub mod options;
+
+/// Quote snapshots
+pub mod quote;
+
+/// Key statistics & valuation measures
+pub mod statistics;
+
 /// Realtime quotes
 mod streaming;
-pub use streaming::Streamer;
+pub use streaming::{ConnectionState, OptionType, QuoteKind, ReconnectPolicy, StreamEvent, StreamQuote, Streamer};
+
+/// Mock Yahoo! servers for testing
+#[cfg(feature = "test-util")]
+pub mod test_util;
 
 /// Symbol profile
 mod profile;
-pub use profile::Profile;
\ No newline at end of file
+pub use profile::{AssetAllocation, Company, Fund, Holding, Profile, Weighting};
\ No newline at end of file
@@ -0,0 +1,165 @@
+use chrono::{DateTime, TimeZone, Utc};
+use futures::{stream, StreamExt, TryStreamExt};
+use snafu::ensure;
+
+use crate::{error, yahoo, Result, YahooClient};
+
+/// A single option contract - a call or a put at one strike price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contract {
+   /// The symbol of the contract itself - ie. 'AAPL200918C00050000'
+   pub contract_symbol: String,
+   pub strike: f64,
+   pub expiration: DateTime<Utc>,
+
+   /// The currency prices are quoted in - ie. 'USD'
+   pub currency: Option<String>,
+
+   pub last_price: Option<f64>,
+   pub change: Option<f64>,
+   pub percent_change: Option<f64>,
+   pub bid: Option<f64>,
+   pub ask: Option<f64>,
+   pub volume: Option<u64>,
+   pub open_interest: Option<u64>,
+   pub implied_volatility: Option<f64>,
+   pub in_the_money: bool,
+
+   /// When the contract last traded - contracts that never traded have no date
+   pub last_trade_date: Option<DateTime<Utc>>
+}
+impl Contract {
+   fn new(data: yahoo::ContractData) -> Contract {
+      Contract {
+         contract_symbol: data.contract_symbol,
+         strike: data.strike,
+         expiration: Utc.timestamp(data.expiration, 0),
+         currency: data.currency,
+         last_price: data.last_price,
+         change: data.change,
+         percent_change: data.percent_change,
+         bid: data.bid,
+         ask: data.ask,
+         volume: data.volume,
+         open_interest: data.open_interest,
+         implied_volatility: data.implied_volatility,
+         in_the_money: data.in_the_money,
+         last_trade_date: data.last_trade_date.map(|time| Utc.timestamp(time, 0))
+      }
+   }
+}
+
+/// The calls and puts on a symbol that expire on the same date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OptionChain {
+   pub underlying_symbol: String,
+   pub expiration: DateTime<Utc>,
+   pub calls: Vec<Contract>,
+   pub puts: Vec<Contract>
+}
+impl OptionChain {
+   fn new(mut data: yahoo::OptionsData) -> Result<OptionChain> {
+      ensure!(!data.options.is_empty(), error::MissingData { reason: "no options were returned" });
+      let options = data.options.swap_remove(0);
+
+      Ok(OptionChain {
+         underlying_symbol: data.underlying_symbol,
+         expiration: Utc.timestamp(options.expiration_date, 0),
+         calls: options.calls.into_iter().map(Contract::new).collect(),
+         puts: options.puts.into_iter().map(Contract::new).collect()
+      })
+   }
+}
+
+/// Retrieves the dates that options on a symbol expire, earliest first.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::options;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for date in options::expirations("AAPL").await.unwrap() {
+///       println!("AAPL options expire on {}", date.format("%b %e %Y"));
+///    }
+/// }
+/// ```
+pub async fn expirations(symbol: &str) -> Result<Vec<DateTime<Utc>>> {
+   YahooClient::shared()?.expirations(symbol).await
+}
+
+/// Retrieves the calls and puts on a symbol that expire on a given date - the
+/// date should be one of the symbol's `expirations`.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::options;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let expiration = options::expirations("AAPL").await.unwrap()[0];
+///    let chain = options::chain("AAPL", expiration).await.unwrap();
+///
+///    for call in &chain.calls {
+///       println!("{} @ {} - bid {:?} / ask {:?}", call.contract_symbol, call.strike, call.bid, call.ask);
+///    }
+/// }
+/// ```
+pub async fn chain(symbol: &str, expiration: DateTime<Utc>) -> Result<OptionChain> {
+   YahooClient::shared()?.chain(symbol, expiration).await
+}
+
+/// Retrieves the calls and puts on a symbol for its nearest expiration date.
+pub async fn nearest_chain(symbol: &str) -> Result<OptionChain> {
+   YahooClient::shared()?.nearest_chain(symbol).await
+}
+
+/// Retrieves the calls and puts on a symbol for every expiration date, earliest
+/// first.  There is one call to Yahoo! per expiration.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::options;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for chain in options::chains("AAPL").await.unwrap() {
+///       println!("{}: {} calls / {} puts", chain.expiration.format("%b %e %Y"), chain.calls.len(), chain.puts.len());
+///    }
+/// }
+/// ```
+pub async fn chains(symbol: &str) -> Result<Vec<OptionChain>> {
+   YahooClient::shared()?.chains(symbol).await
+}
+
+impl YahooClient {
+   /// Retrieves the dates that options on a symbol expire - see [`options::expirations`](options/fn.expirations.html).
+   pub async fn expirations(&self, symbol: &str) -> Result<Vec<DateTime<Utc>>> {
+      let data = yahoo::load_options(self, symbol, None).await?;
+      Ok(data.expiration_dates.into_iter().map(|date| Utc.timestamp(date, 0)).collect())
+   }
+
+   /// Retrieves the options on a symbol for an expiration date - see [`options::chain`](options/fn.chain.html).
+   pub async fn chain(&self, symbol: &str, expiration: DateTime<Utc>) -> Result<OptionChain> {
+      OptionChain::new(yahoo::load_options(self, symbol, Some(expiration.timestamp())).await?)
+   }
+
+   /// Retrieves the options on a symbol for its nearest expiration date - see [`options::nearest_chain`](options/fn.nearest_chain.html).
+   pub async fn nearest_chain(&self, symbol: &str) -> Result<OptionChain> {
+      OptionChain::new(yahoo::load_options(self, symbol, None).await?)
+   }
+
+   /// Retrieves the options on a symbol for every expiration date - see [`options::chains`](options/fn.chains.html).
+   pub async fn chains(&self, symbol: &str) -> Result<Vec<OptionChain>> {
+      let expirations = self.expirations(symbol).await?;
+
+      stream::iter(expirations)
+         .map(|expiration| self.chain(symbol, expiration))
+         .buffered(self.concurrency())
+         .try_collect()
+         .await
+   }
+}
@@ -1,4 +1,9 @@
-use crate::{error, yahoo, Result};
+use chrono::{DateTime, Utc};
+use snafu::OptionExt;
+use std::collections::HashMap;
+
+use crate::yahoo::{raw_date, raw_number};
+use crate::{error, yahoo, Result, YahooClient};
 
 /// Symbols which represent a company can have an address associated with them.
 /// This is usually the company headquarters.
@@ -48,7 +53,7 @@ pub struct Company {
 }
 impl Company {
    fn new(data: yahoo::QuoteSummaryStore) -> Result<Company> {
-      let profile = data.company_profile.expect("asdf");
+      let profile = data.company_profile.context(error::MissingData { reason: "no company profile" })?;
       let address = Some(Address::new(&profile)?);
 
       Ok(Company {
@@ -62,23 +67,108 @@ impl Company {
    }
 }
 
+/// A single holding of a fund.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Holding {
+   pub symbol: Option<String>,
+   pub name: Option<String>,
+
+   /// The fraction of the fund in the holding - ie. 0.12 is 12%
+   pub weight: Option<f64>
+}
+
+/// A named share of a fund - ie. a sector or a bond rating - and its weight as
+/// a fraction of the fund.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weighting {
+   pub name: String,
+   pub weight: f64
+}
+
+/// How a fund is split between asset classes, as fractions of the fund.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetAllocation {
+   pub stock: Option<f64>,
+   pub bond: Option<f64>,
+   pub cash: Option<f64>,
+   pub preferred: Option<f64>,
+   pub convertible: Option<f64>,
+   pub other: Option<f64>
+}
+
+/// Helper function to flatten Yahoo!'s list of single entry weightings
+fn weightings(data: Vec<HashMap<String, yahoo::RawNumber>>) -> Vec<Weighting> {
+   data.into_iter()
+      .flat_map(|weighting| weighting.into_iter())
+      .filter_map(|(name, weight)| Some(Weighting { name, weight: weight.raw? }))
+      .collect()
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct Fund {
    pub name: String,
 
    pub family: Option<String>,
 
-   pub kind: String
+   pub kind: String,
+
+   /// The category, according to Yahoo.  ie. 'Large Growth'
+   pub category: Option<String>,
+
+   /// The annual expense ratio as a fraction - ie. 0.002 is 0.2%
+   pub expense_ratio: Option<f64>,
+
+   /// The annual holdings turnover as a fraction
+   pub turnover: Option<f64>,
+
+   pub inception_date: Option<DateTime<Utc>>,
+   pub total_assets: Option<f64>,
+
+   /// The largest holdings, biggest first
+   pub holdings: Vec<Holding>,
+
+   pub sector_weightings: Vec<Weighting>,
+   pub bond_ratings: Vec<Weighting>,
+   pub asset_allocation: Option<AssetAllocation>
 }
 impl Fund {
    fn new(data: yahoo::QuoteSummaryStore) -> Result<Fund> {
-      let profile = data.fund_profile.expect("asdf");
+      let profile = data.fund_profile.context(error::MissingData { reason: "no fund profile" })?;
+      let fees = profile.fees;
+      let stats = data.default_key_statistics;
 
-      Ok(Fund {
+      let mut fund = Fund {
          name: data.quote_type.name,
          kind: profile.kind,
-         family: profile.family
-      })
+         family: profile.family,
+         category: profile.category,
+         expense_ratio: raw_number(fees.as_ref().and_then(|fees| fees.annual_report_expense_ratio.clone())),
+         turnover: raw_number(fees.and_then(|fees| fees.annual_holdings_turnover)),
+         inception_date: raw_date(stats.as_ref().and_then(|stats| stats.fund_inception_date.clone())),
+         total_assets: raw_number(stats.and_then(|stats| stats.total_assets)),
+         holdings: Vec::new(),
+         sector_weightings: Vec::new(),
+         bond_ratings: Vec::new(),
+         asset_allocation: None
+      };
+
+      if let Some(top) = data.top_holdings {
+         fund.holdings = top.holdings.into_iter()
+            .map(|holding| Holding { symbol: holding.symbol, name: holding.holding_name, weight: raw_number(holding.holding_percent) })
+            .collect();
+         fund.sector_weightings = weightings(top.sector_weightings);
+         fund.bond_ratings = weightings(top.bond_ratings);
+         fund.asset_allocation = Some(AssetAllocation {
+            stock: raw_number(top.stock_position),
+            bond: raw_number(top.bond_position),
+            cash: raw_number(top.cash_position),
+            preferred: raw_number(top.preferred_position),
+            convertible: raw_number(top.convertible_position),
+            other: raw_number(top.other_position)
+         });
+      }
+
+      Ok(fund)
    }
 }
 
@@ -89,12 +179,19 @@ pub enum Profile {
 }
 impl Profile {
    pub async fn load(symbol: &str) -> Result<Profile> {
-      let data = yahoo::scrape(symbol).await?.quote_summary_store;
+      YahooClient::shared()?.profile(symbol).await
+   }
+}
+
+impl YahooClient {
+   /// Loads the profile for a symbol - see `Profile::load`.
+   pub async fn profile(&self, symbol: &str) -> Result<Profile> {
+      let data = yahoo::scrape(self, symbol).await?.quote_summary_store;
 
       let kind = &data.quote_type.kind;
       match kind.as_str() {
-         "EQUITY" => Ok(Self::Company(Company::new(data)?)),
-         "ETF" => Ok(Self::Fund(Fund::new(data)?)),
+         "EQUITY" => Ok(Profile::Company(Company::new(data)?)),
+         "ETF" | "MUTUALFUND" => Ok(Profile::Fund(Fund::new(data)?)),
          _ => (error::UnsupportedSecurity { kind }).fail().map_err(core::convert::Into::into)
       }
    }
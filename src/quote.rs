@@ -0,0 +1,157 @@
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::{yahoo, Result, YahooClient};
+
+/// The most symbols we ask Yahoo! for in a single call
+const MAX_SYMBOLS: usize = 200;
+
+/// The state of the market a symbol trades on when the snapshot was taken.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MarketState {
+   PreMarket,
+   Regular,
+   PostMarket,
+   Closed,
+   Other(String)
+}
+impl MarketState {
+   fn new(value: &str) -> MarketState {
+      match value {
+         "PRE" => MarketState::PreMarket,
+         "REGULAR" => MarketState::Regular,
+         "POST" | "POSTPOST" => MarketState::PostMarket,
+         // PREPRE is overnight - before the pre-market opens
+         "CLOSED" | "PREPRE" => MarketState::Closed,
+         _ => MarketState::Other(value.to_string())
+      }
+   }
+}
+
+/// Trading outside of regular market hours
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtendedHours {
+   pub price: f64,
+   pub change: Option<f64>,
+   pub change_percent: Option<f64>,
+   pub timestamp: Option<DateTime<Utc>>
+}
+impl ExtendedHours {
+   fn new(price: Option<f64>, change: Option<f64>, change_percent: Option<f64>, time: Option<i64>) -> Option<ExtendedHours> {
+      Some(ExtendedHours { price: price?, change, change_percent, timestamp: time.map(|time| Utc.timestamp(time, 0)) })
+   }
+}
+
+/// The current state of a symbol.  Yahoo! leaves out whatever doesn't apply to
+/// the symbol (ie. indices have no bid / ask) so most everything is optional.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+   pub symbol: String,
+
+   /// The common name for the symbol.
+   pub name: Option<String>,
+
+   /// The currency prices are quoted in - ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The exchange code, according to Yahoo.  ie. 'NMS'
+   pub exchange: Option<String>,
+
+   /// The kind of security - ie. 'EQUITY' or 'INDEX'
+   pub kind: Option<String>,
+
+   pub market_state: Option<MarketState>,
+
+   /// When the regular market price was last updated
+   pub timestamp: Option<DateTime<Utc>>,
+
+   pub price: Option<f64>,
+   pub change: Option<f64>,
+   pub change_percent: Option<f64>,
+   pub open: Option<f64>,
+   pub day_high: Option<f64>,
+   pub day_low: Option<f64>,
+   pub previous_close: Option<f64>,
+   pub volume: Option<u64>,
+
+   /// The average daily volume over the last 3 months
+   pub average_volume: Option<u64>,
+
+   pub bid: Option<f64>,
+   pub bid_size: Option<u64>,
+   pub ask: Option<f64>,
+   pub ask_size: Option<u64>,
+
+   pub fifty_two_week_low: Option<f64>,
+   pub fifty_two_week_high: Option<f64>,
+
+   pub market_cap: Option<u64>,
+   pub trailing_pe: Option<f64>,
+   pub forward_pe: Option<f64>,
+
+   pub pre_market: Option<ExtendedHours>,
+   pub post_market: Option<ExtendedHours>
+}
+impl Snapshot {
+   fn new(data: yahoo::QuoteData) -> Snapshot {
+      Snapshot {
+         pre_market: ExtendedHours::new(data.pre_market_price, data.pre_market_change, data.pre_market_change_percent, data.pre_market_time),
+         post_market: ExtendedHours::new(data.post_market_price, data.post_market_change, data.post_market_change_percent, data.post_market_time),
+         symbol: data.symbol,
+         name: data.long_name.or(data.short_name),
+         currency: data.currency,
+         exchange: data.exchange,
+         kind: data.quote_type,
+         market_state: data.market_state.as_deref().map(MarketState::new),
+         timestamp: data.regular_market_time.map(|time| Utc.timestamp(time, 0)),
+         price: data.regular_market_price,
+         change: data.regular_market_change,
+         change_percent: data.regular_market_change_percent,
+         open: data.regular_market_open,
+         day_high: data.regular_market_day_high,
+         day_low: data.regular_market_day_low,
+         previous_close: data.regular_market_previous_close,
+         volume: data.regular_market_volume,
+         average_volume: data.average_volume,
+         bid: data.bid,
+         bid_size: data.bid_size,
+         ask: data.ask,
+         ask_size: data.ask_size,
+         fifty_two_week_low: data.fifty_two_week_low,
+         fifty_two_week_high: data.fifty_two_week_high,
+         market_cap: data.market_cap,
+         trailing_pe: data.trailing_pe,
+         forward_pe: data.forward_pe
+      }
+   }
+}
+
+/// Retrieves a snapshot of the current state of many symbols at once.
+///
+/// Symbols Yahoo! doesn't know about are left out of the results.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::quote;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for snapshot in quote::snapshot(&["AAPL", "MSFT", "^DJI"]).await.unwrap() {
+///       println!("{} is trading at {:?} ({:?} / {:?})", snapshot.symbol, snapshot.price, snapshot.bid, snapshot.ask);
+///    }
+/// }
+/// ```
+pub async fn snapshot(symbols: &[&str]) -> Result<Vec<Snapshot>> {
+   YahooClient::shared()?.snapshot(symbols).await
+}
+
+impl YahooClient {
+   /// Retrieves a snapshot of the current state of many symbols at once - see [`quote::snapshot`](quote/fn.snapshot.html).
+   pub async fn snapshot(&self, symbols: &[&str]) -> Result<Vec<Snapshot>> {
+      let mut result = Vec::new();
+      for chunk in symbols.chunks(MAX_SYMBOLS) {
+         result.extend(yahoo::load_quotes(self, chunk).await?.into_iter().map(Snapshot::new));
+      }
+      Ok(result)
+   }
+}
@@ -0,0 +1,111 @@
+use futures::lock::Mutex;
+use reqwest::header::{COOKIE, SET_COOKIE};
+use reqwest::Url;
+use snafu::{ensure, ResultExt};
+use std::sync::Arc;
+
+use crate::throttle::RateLimiter;
+use crate::{error, Result};
+
+/// A Yahoo! session - the cookie identifying us along with the crumb that
+/// has to accompany it on every call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+   pub cookie: String,
+   pub crumb: String
+}
+
+#[derive(Debug, Default)]
+struct State {
+   session: Option<Session>,
+
+   /// Whether the last attempt to start a session failed
+   failed: bool
+}
+
+/// Obtains sessions from Yahoo! and caches them until they are rejected.
+///
+/// Only one session is started at a time - concurrent calls wait for it rather
+/// than each starting their own.
+#[derive(Debug, Clone)]
+pub struct SessionCache {
+   cookie_url: Url,
+   crumb_url: Url,
+
+   /// The client's rate limiter - starting a session counts towards it too
+   limiter: Option<RateLimiter>,
+   state: Arc<Mutex<State>>
+}
+impl SessionCache {
+   pub(crate) fn new(cookie_url: Url, crumb_url: Url, limiter: Option<RateLimiter>) -> SessionCache {
+      SessionCache { cookie_url, crumb_url, limiter, state: Arc::new(Mutex::new(State::default())) }
+   }
+
+   /// The cached session - starting a new one if we don't have one yet.  Most
+   /// calls work without a session, so failing to start one isn't an error -
+   /// there is no session until Yahoo! rejects a call and it is renewed.
+   pub async fn get(&self, http: &reqwest::Client) -> Option<Session> {
+      let mut state = self.state.lock().await;
+      if state.session.is_none() && !state.failed {
+         match self.start(http).await {
+            Ok(session) => state.session = Some(session),
+            Err(_) => state.failed = true
+         }
+      }
+      state.session.clone()
+   }
+
+   /// Starts a new session to replace the one Yahoo! rejected - unless another
+   /// call already replaced it.
+   pub async fn renew(&self, http: &reqwest::Client, rejected: Option<&Session>) -> Result<Session> {
+      let mut state = self.state.lock().await;
+      if let Some(session) = &state.session {
+         if Some(session) != rejected { return Ok(session.clone()); }
+      }
+
+      state.session = None;
+      match self.start(http).await {
+         Ok(session) => {
+            *state = State { session: Some(session.clone()), failed: false };
+            Ok(session)
+         },
+         Err(err) => {
+            state.failed = true;
+            Err(err)
+         }
+      }
+   }
+
+   async fn start(&self, http: &reqwest::Client) -> Result<Session> {
+      // the cookie comes back with the response no matter what the status is
+      self.acquire().await;
+      let response = http.get(self.cookie_url.clone()).send().await.context(error::RequestFailed)?;
+      let cookie = response.headers().get_all(SET_COOKIE).iter()
+         .filter_map(|value| value.to_str().ok())
+         .filter_map(|value| value.split(';').next())
+         .map(str::trim)
+         .filter(|value| !value.is_empty())
+         .collect::<Vec<_>>()
+         .join("; ");
+      ensure!(!cookie.is_empty(), error::SessionFailed { reason: "no cookie was set" });
+
+      // the crumb is only handed out to callers with a cookie
+      self.acquire().await;
+      let response = http.get(self.crumb_url.clone()).header(COOKIE, cookie.as_str()).send().await.context(error::RequestFailed)?;
+      ensure!(
+         response.status().is_success(),
+         error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
+      );
+
+      let crumb = response.text().await.context(error::UnexpectedErrorRead { url: self.crumb_url.to_string() })?;
+      let crumb = crumb.trim();
+      ensure!(!crumb.is_empty() && !crumb.contains(char::is_whitespace), error::SessionFailed { reason: "no crumb was returned" });
+
+      Ok(Session { cookie, crumb: crumb.to_string() })
+   }
+
+   /// Waits for the rate limiter, if the client has one
+   async fn acquire(&self) {
+      if let Some(limiter) = &self.limiter { limiter.acquire().await; }
+   }
+}
@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+
+use crate::yahoo::{self, raw_date, raw_number};
+use crate::{Result, YahooClient};
+
+/// The quote summary modules the statistics come from
+const MODULES: &[&str] = &["defaultKeyStatistics", "summaryDetail"];
+
+/// Valuation measures and key statistics for a symbol.  Yahoo! leaves out
+/// whatever doesn't apply to the symbol (ie. funds have no enterprise value) so
+/// everything is optional.  Ratios and percentages are fractions - ie. 0.25 is
+/// 25%.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statistics {
+   pub market_cap: Option<u64>,
+   pub enterprise_value: Option<f64>,
+
+   pub trailing_pe: Option<f64>,
+   pub forward_pe: Option<f64>,
+   pub peg_ratio: Option<f64>,
+   pub price_to_book: Option<f64>,
+   pub price_to_sales: Option<f64>,
+   pub enterprise_to_revenue: Option<f64>,
+   pub enterprise_to_ebitda: Option<f64>,
+
+   pub trailing_eps: Option<f64>,
+   pub forward_eps: Option<f64>,
+
+   /// Book value per share
+   pub book_value: Option<f64>,
+
+   pub beta: Option<f64>,
+
+   pub shares_outstanding: Option<u64>,
+   pub float_shares: Option<u64>,
+   pub shares_short: Option<u64>,
+
+   /// Days to cover the short interest at the average daily volume
+   pub short_ratio: Option<f64>,
+   pub short_percent_of_float: Option<f64>,
+
+   /// The annual dividend per share
+   pub dividend_rate: Option<f64>,
+   pub dividend_yield: Option<f64>,
+   pub payout_ratio: Option<f64>,
+   pub ex_dividend_date: Option<DateTime<Utc>>,
+
+   pub fifty_two_week_low: Option<f64>,
+   pub fifty_two_week_high: Option<f64>,
+   pub fifty_day_average: Option<f64>,
+   pub two_hundred_day_average: Option<f64>,
+   pub average_volume: Option<u64>
+}
+impl Statistics {
+   fn new(data: yahoo::QuoteSummaryData) -> Statistics {
+      // either module can be missing
+      let stats = data.default_key_statistics;
+      let detail = data.summary_detail;
+
+      Statistics {
+         market_cap: raw_number(detail.as_ref().and_then(|detail| detail.market_cap.clone())).map(|cap| cap as u64),
+         enterprise_value: raw_number(stats.as_ref().and_then(|stats| stats.enterprise_value.clone())),
+         trailing_pe: raw_number(detail.as_ref().and_then(|detail| detail.trailing_pe.clone())),
+         forward_pe: raw_number(stats.as_ref().and_then(|stats| stats.forward_pe.clone()))
+            .or_else(|| raw_number(detail.as_ref().and_then(|detail| detail.forward_pe.clone()))),
+         peg_ratio: raw_number(stats.as_ref().and_then(|stats| stats.peg_ratio.clone())),
+         price_to_book: raw_number(stats.as_ref().and_then(|stats| stats.price_to_book.clone())),
+         price_to_sales: raw_number(detail.as_ref().and_then(|detail| detail.price_to_sales.clone())),
+         enterprise_to_revenue: raw_number(stats.as_ref().and_then(|stats| stats.enterprise_to_revenue.clone())),
+         enterprise_to_ebitda: raw_number(stats.as_ref().and_then(|stats| stats.enterprise_to_ebitda.clone())),
+         trailing_eps: raw_number(stats.as_ref().and_then(|stats| stats.trailing_eps.clone())),
+         forward_eps: raw_number(stats.as_ref().and_then(|stats| stats.forward_eps.clone())),
+         book_value: raw_number(stats.as_ref().and_then(|stats| stats.book_value.clone())),
+         beta: raw_number(detail.as_ref().and_then(|detail| detail.beta.clone()))
+            .or_else(|| raw_number(stats.as_ref().and_then(|stats| stats.beta.clone()))),
+         shares_outstanding: raw_number(stats.as_ref().and_then(|stats| stats.shares_outstanding.clone())).map(|shares| shares as u64),
+         float_shares: raw_number(stats.as_ref().and_then(|stats| stats.float_shares.clone())).map(|shares| shares as u64),
+         shares_short: raw_number(stats.as_ref().and_then(|stats| stats.shares_short.clone())).map(|shares| shares as u64),
+         short_ratio: raw_number(stats.as_ref().and_then(|stats| stats.short_ratio.clone())),
+         short_percent_of_float: raw_number(stats.as_ref().and_then(|stats| stats.short_percent_of_float.clone())),
+         dividend_rate: raw_number(detail.as_ref().and_then(|detail| detail.dividend_rate.clone())),
+         dividend_yield: raw_number(detail.as_ref().and_then(|detail| detail.dividend_yield.clone())),
+         payout_ratio: raw_number(detail.as_ref().and_then(|detail| detail.payout_ratio.clone())),
+         ex_dividend_date: raw_date(detail.as_ref().and_then(|detail| detail.ex_dividend_date.clone())),
+         fifty_two_week_low: raw_number(detail.as_ref().and_then(|detail| detail.fifty_two_week_low.clone())),
+         fifty_two_week_high: raw_number(detail.as_ref().and_then(|detail| detail.fifty_two_week_high.clone())),
+         fifty_day_average: raw_number(detail.as_ref().and_then(|detail| detail.fifty_day_average.clone())),
+         two_hundred_day_average: raw_number(detail.as_ref().and_then(|detail| detail.two_hundred_day_average.clone())),
+         average_volume: raw_number(detail.as_ref().and_then(|detail| detail.average_volume.clone())).map(|volume| volume as u64)
+      }
+   }
+}
+
+/// Retrieves the key statistics and valuation measures for a symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::statistics;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let stats = statistics::retrieve("AAPL").await.unwrap();
+///    println!("AAPL trades at {:?}x earnings with a beta of {:?}", stats.trailing_pe, stats.beta);
+/// }
+/// ```
+pub async fn retrieve(symbol: &str) -> Result<Statistics> {
+   YahooClient::shared()?.statistics(symbol).await
+}
+
+impl YahooClient {
+   /// Retrieves the key statistics for a symbol - see [`statistics::retrieve`](statistics/fn.retrieve.html).
+   pub async fn statistics(&self, symbol: &str) -> Result<Statistics> {
+      Ok(Statistics::new(yahoo::load_summary(self, symbol, MODULES).await?))
+   }
+}
@@ -1,18 +1,31 @@
 use base64::decode;
-use futures::{ future, Stream, SinkExt, StreamExt };
+use futures::channel::mpsc::{ unbounded, UnboundedReceiver, UnboundedSender };
+use futures::future::{ self, AbortHandle, Aborted };
+use futures::{ Future, Stream, SinkExt, StreamExt };
 use protobuf::parse_from_bytes;
 use serde::Serialize;
-use std::sync::{ mpsc, Arc, Mutex };
-use tokio_tungstenite::{ connect_async, tungstenite::protocol::Message };
+use snafu::ResultExt;
+use std::pin::Pin;
+use std::sync::{ Arc, Mutex };
+use std::task::{ Context, Poll };
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::{ delay_for, Instant };
+use tokio_tungstenite::{ connect_async, tungstenite::client::IntoClientRequest, tungstenite::protocol::Message };
+use tokio_tungstenite::tungstenite::{ self, handshake::client::Request };
 
-use crate::{ TradingSession };
-use crate::yahoo::{ PricingData, PricingData_MarketHoursType };
+use crate::throttle::backoff;
+use crate::{ error, Error, Result, TradingSession, YahooClient };
+use crate::yahoo::{ PricingData, PricingData_MarketHoursType, PricingData_OptionType, PricingData_QuoteType };
 
 use super::{ Quote };
 
+/// The messages Yahoo! accepts - ie. `{"subscribe": ["AAPL"]}`
 #[derive(Debug, Clone, Serialize)]
-struct Subs {
-   subscribe: Vec<String>,
+#[serde(rename_all = "lowercase")]
+enum Subs {
+   Subscribe(Vec<String>),
+   Unsubscribe(Vec<String>)
 }
 
 fn convert_session(value: PricingData_MarketHoursType) -> TradingSession {
@@ -24,76 +37,667 @@ fn convert_session(value: PricingData_MarketHoursType) -> TradingSession {
    }
 }
 
+/// The kind of security a streamed quote is for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteKind {
+   None,
+   AltSymbol,
+   Heartbeat,
+   Equity,
+   Index,
+   MutualFund,
+   MoneyMarket,
+   Option,
+   Currency,
+   Warrant,
+   Bond,
+   Future,
+   Etf,
+   Commodity,
+   EcnQuote,
+   Cryptocurrency,
+   Indicator,
+   Industry
+}
+impl From<PricingData_QuoteType> for QuoteKind {
+   fn from(value: PricingData_QuoteType) -> QuoteKind {
+      match value {
+         PricingData_QuoteType::NONE => QuoteKind::None,
+         PricingData_QuoteType::ALTSYMBOL => QuoteKind::AltSymbol,
+         PricingData_QuoteType::HEARTBEAT => QuoteKind::Heartbeat,
+         PricingData_QuoteType::EQUITY => QuoteKind::Equity,
+         PricingData_QuoteType::INDEX => QuoteKind::Index,
+         PricingData_QuoteType::MUTUALFUND => QuoteKind::MutualFund,
+         PricingData_QuoteType::MONEYMARKET => QuoteKind::MoneyMarket,
+         PricingData_QuoteType::OPTION => QuoteKind::Option,
+         PricingData_QuoteType::CURRENCY => QuoteKind::Currency,
+         PricingData_QuoteType::WARRANT => QuoteKind::Warrant,
+         PricingData_QuoteType::BOND => QuoteKind::Bond,
+         PricingData_QuoteType::FUTURE => QuoteKind::Future,
+         PricingData_QuoteType::ETF => QuoteKind::Etf,
+         PricingData_QuoteType::COMMODITY => QuoteKind::Commodity,
+         PricingData_QuoteType::ECNQUOTE => QuoteKind::EcnQuote,
+         PricingData_QuoteType::CRYPTOCURRENCY => QuoteKind::Cryptocurrency,
+         PricingData_QuoteType::INDICATOR => QuoteKind::Indicator,
+         PricingData_QuoteType::INDUSTRY => QuoteKind::Industry
+      }
+   }
+}
+
+/// Whether an option is a call or a put
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptionType { Call, Put }
+impl From<PricingData_OptionType> for OptionType {
+   fn from(value: PricingData_OptionType) -> OptionType {
+      match value {
+         PricingData_OptionType::CALL => OptionType::Call,
+         PricingData_OptionType::PUT => OptionType::Put
+      }
+   }
+}
+
+/// Everything Yahoo! streams about a symbol.  Yahoo! only sends what applies
+/// to the symbol, so anything that doesn't is zero (or empty).
+#[derive(Debug, Clone)]
+pub struct StreamQuote {
+   pub symbol: String,
+   pub kind: QuoteKind,
+   pub short_name: String,
+   pub currency: String,
+   pub exchange: String,
+
+   /// Milliseconds since the epoch
+   pub timestamp: i64,
+   pub session: TradingSession,
+
+   pub price: f64,
+   pub change: f64,
+   pub change_percent: f64,
+   pub open: f64,
+   pub day_high: f64,
+   pub day_low: f64,
+   pub previous_close: f64,
+   pub day_volume: i64,
+   pub last_size: i64,
+
+   pub bid: f64,
+   pub bid_size: i64,
+   pub ask: f64,
+   pub ask_size: i64,
+
+   /// The number of decimal places prices are usually shown with
+   pub price_hint: i64,
+
+   /// Options only - whether the option is a call or a put
+   pub option_type: Option<OptionType>,
+   pub underlying_symbol: String,
+   pub strike: f64,
+   pub expire_date: i64,
+   pub open_interest: i64,
+   pub mini_option: i64,
+
+   /// Cryptocurrencies only
+   pub volume_24hr: i64,
+   pub volume_all_currencies: i64,
+   pub from_currency: String,
+   pub last_market: String,
+   pub circulating_supply: f64,
+   pub market_cap: f64
+}
+impl From<PricingData> for StreamQuote {
+   fn from(data: PricingData) -> StreamQuote {
+      let kind = QuoteKind::from(data.quoteType);
+
+      StreamQuote {
+         symbol: data.id,
+         kind,
+         short_name: data.shortName,
+         currency: data.currency,
+         exchange: data.exchange,
+         timestamp: data.time,
+         session: convert_session(data.marketHours),
+         price: f64::from(data.price),
+         change: f64::from(data.change),
+         change_percent: f64::from(data.changePercent),
+         open: f64::from(data.openPrice),
+         day_high: f64::from(data.dayHigh),
+         day_low: f64::from(data.dayLow),
+         previous_close: f64::from(data.previousClose),
+         day_volume: data.dayVolume,
+         last_size: data.lastSize,
+         bid: f64::from(data.bid),
+         bid_size: data.bidSize,
+         ask: f64::from(data.ask),
+         ask_size: data.askSize,
+         price_hint: data.priceHint,
+         option_type: if kind == QuoteKind::Option { Some(OptionType::from(data.optionsType)) } else { None },
+         underlying_symbol: data.underlyingSymbol,
+         strike: f64::from(data.strikePrice),
+         expire_date: data.expireDate,
+         open_interest: data.openInterest,
+         mini_option: data.miniOption,
+         volume_24hr: data.vol_24hr,
+         volume_all_currencies: data.volAllCurrencies,
+         from_currency: data.fromcurrency,
+         last_market: data.lastMarket,
+         circulating_supply: data.circulatingSupply,
+         market_cap: data.marketcap
+      }
+   }
+}
+impl From<StreamQuote> for Quote {
+   fn from(quote: StreamQuote) -> Quote {
+      Quote {
+         symbol: quote.symbol,
+         timestamp: quote.timestamp,
+         session: quote.session,
+         price: quote.price,
+         volume: quote.day_volume as u64
+      }
+   }
+}
+
+/// Builds the websocket request - with the same headers (ie. user agent) as
+/// every other call.
+fn request(client: &YahooClient) -> tungstenite::Result<Request> {
+   let mut request = client.streamer_url().into_client_request()?;
+   for (name, value) in client.headers() { request.headers_mut().insert(name.clone(), value.clone()); }
+   Ok(request)
+}
+
+/// Decodes a quote frame - base64 encoded protobuf `PricingData`.
+fn parse_quote(data: &[u8]) -> Result<StreamQuote> {
+   let bytes = decode(data).context(error::DecodeFailed)?;
+   let data = parse_from_bytes::<PricingData>(&bytes).context(error::ProtocolFailed)?;
+   Ok(StreamQuote::from(data))
+}
+
+/// How a resilient stream (see `Streamer::stream_resilient`) detects dead
+/// connections and reconnects.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReconnectPolicy {
+   /// How long to go without any message from Yahoo! (data or ping) before the
+   /// connection is treated as dead
+   pub stale_after: Duration,
+
+   /// The delay before the first reconnect - later reconnects back off exponentially
+   pub base_delay: Duration,
+
+   /// The longest we'll wait between reconnects
+   pub max_delay: Duration,
+
+   /// The most reconnects in a row before giving up - `None` never gives up
+   pub max_attempts: Option<u32>
+}
+impl Default for ReconnectPolicy {
+   /// Treat a minute without messages as stale and keep reconnecting, starting at a second
+   fn default() -> ReconnectPolicy {
+      ReconnectPolicy { stale_after: Duration::from_secs(60), base_delay: Duration::from_secs(1), max_delay: Duration::from_secs(60), max_attempts: None }
+   }
+}
+
+/// The state of a resilient stream's connection to Yahoo!
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectionState {
+   Connecting,
+   Connected,
+
+   /// The connection failed, dropped or went stale
+   Disconnected { reason: String },
+
+   /// Waiting to reconnect - `attempt` counts the reconnects since data last came through
+   Reconnecting { attempt: u32, delay: Duration }
+}
+
+/// Everything a resilient stream sends - quotes, interleaved with changes to
+/// the connection.
+#[derive(Debug)]
+pub enum StreamEvent {
+   State(ConnectionState),
+   Quote(StreamQuote),
+
+   /// A frame that could not be read - the stream carries on past it
+   Error(Error)
+}
+
+/// Wraps up a decoded quote (or the reason it couldn't be decoded) for a
+/// resilient stream.
+fn quote_event(quote: Result<StreamQuote>) -> StreamEvent {
+   match quote {
+      Ok(quote) => StreamEvent::Quote(quote),
+      Err(err) => StreamEvent::Error(err)
+   }
+}
+
+/// Keeps a resilient stream connected - reconnecting and resubscribing when
+/// the connection is lost.
+struct Supervisor {
+   client: YahooClient,
+   subs: Arc<Mutex<Vec<String>>>,
+   sender: Arc<Mutex<Option<UnboundedSender<Message>>>>,
+   shutdown: Arc<Mutex<bool>>,
+   policy: ReconnectPolicy,
+   events: UnboundedSender<StreamEvent>
+}
+impl Supervisor {
+   async fn run(self) {
+      let mut attempt = 0;
+      loop {
+         self.emit(StreamEvent::State(ConnectionState::Connecting));
+         let reason = self.connection(&mut attempt).await;
+
+         // nobody to reconnect for
+         if self.finished() { return; }
+         self.emit(StreamEvent::State(ConnectionState::Disconnected { reason }));
+
+         if let Some(max_attempts) = self.policy.max_attempts {
+            if attempt >= max_attempts { return; }
+         }
+         let delay = backoff(self.policy.base_delay, self.policy.max_delay, attempt);
+         attempt += 1;
+
+         self.emit(StreamEvent::State(ConnectionState::Reconnecting { attempt, delay }));
+         delay_for(delay).await;
+         if self.finished() { return; }
+      }
+   }
+
+   /// Connects and streams until the connection is lost - returning why.
+   ///
+   /// The reconnect attempts only start over once data comes through, so that
+   /// a server that accepts and then drops us still backs off and gives up.
+   async fn connection(&self, attempt: &mut u32) -> String {
+      let request = match request(&self.client) {
+         Ok(request) => request,
+         Err(err) => return err.to_string()
+      };
+      let (mut sink, mut source) = match connect_async(request).await {
+         Ok((stream, _)) => stream.split(),
+         Err(err) => return err.to_string()
+      };
+
+      self.emit(StreamEvent::State(ConnectionState::Connected));
+
+      // resubscribe - holding on to the symbols until the sender is in place so
+      // that no subscription changes are missed
+      let (tx, mut rx) = unbounded();
+      {
+         let subs = self.subs.lock().unwrap();
+         if !subs.is_empty() {
+            if let Ok(message) = serde_json::to_string(&Subs::Subscribe(subs.clone())) { let _ = tx.unbounded_send(Message::Text(message)); }
+         }
+         *self.sender.lock().unwrap() = Some(tx);
+      }
+
+      // the stream may have been dropped while connecting - with no sender to
+      // close the connection through yet
+      if self.finished() {
+         let _ = sink.send(Message::Close(None)).await;
+         *self.sender.lock().unwrap() = None;
+         return "the stream was stopped".to_string();
+      }
+
+      // only messages from Yahoo! push the deadline back - not the ones we send
+      let mut stale = delay_for(self.policy.stale_after);
+      let reason = loop {
+         tokio::select! {
+            _ = &mut stale => break format!("no messages for {} seconds", self.policy.stale_after.as_secs()),
+            msg = source.next() => {
+               stale.reset(Instant::now() + self.policy.stale_after);
+               match msg {
+                  None => break "the connection was closed".to_string(),
+                  Some(Err(err)) => break err.to_string(),
+                  Some(Ok(Message::Close(_))) => break "Yahoo! closed the connection".to_string(),
+                  Some(Ok(Message::Ping(data))) => {
+                     if let Err(err) = sink.send(Message::Pong(data)).await { break err.to_string(); }
+                  },
+                  // frames that can't be decoded are reported - the connection itself is fine
+                  Some(Ok(Message::Text(value))) => {
+                     *attempt = 0;
+                     self.emit(quote_event(parse_quote(value.as_bytes())));
+                  },
+                  Some(Ok(Message::Binary(value))) => {
+                     *attempt = 0;
+                     self.emit(quote_event(parse_quote(&value)));
+                  },
+                  Some(Ok(_)) => {}
+               }
+            },
+            Some(msg) = rx.next() => {
+               let closing = matches!(msg, Message::Close(_));
+               if let Err(err) = sink.send(msg).await { break err.to_string(); }
+               if closing { break "the stream was stopped".to_string(); }
+            }
+         }
+
+         if self.finished() {
+            let _ = sink.send(Message::Close(None)).await;
+            break "the stream was stopped".to_string();
+         }
+      };
+
+      // subscription changes go out in full with the next connection
+      *self.sender.lock().unwrap() = None;
+      reason
+   }
+
+   fn emit(&self, event: StreamEvent) {
+      let _ = self.events.unbounded_send(event);
+   }
+
+   /// Whether the streamer was stopped or the consumer went away
+   fn finished(&self) -> bool {
+      *self.shutdown.lock().unwrap() || self.events.is_closed()
+   }
+}
+
+/// Closes the connection when the stream it belongs to is dropped.
+struct Closer(UnboundedSender<Message>);
+impl Drop for Closer {
+   fn drop(&mut self) {
+      let _ = self.0.unbounded_send(Message::Close(None));
+   }
+}
+
+/// The events of a resilient stream - stopping its supervisor, and closing the
+/// connection, when dropped.
+struct ResilientStream {
+   events: UnboundedReceiver<StreamEvent>,
+   shutdown: Arc<Mutex<bool>>,
+   sender: Arc<Mutex<Option<UnboundedSender<Message>>>>
+}
+impl Stream for ResilientStream {
+   type Item = StreamEvent;
+
+   fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<StreamEvent>> {
+      self.events.poll_next_unpin(cx)
+   }
+}
+impl Drop for ResilientStream {
+   fn drop(&mut self) {
+      // once shut down the connection (if any) belongs to a newer stream
+      let mut shutdown = self.shutdown.lock().unwrap();
+      if *shutdown { return; }
+      *shutdown = true;
+
+      if let Some(tx) = self.sender.lock().unwrap().as_ref() {
+         let _ = tx.unbounded_send(Message::Close(None));
+      }
+   }
+}
+
+/// The task running a stream - either the writer or the resilient supervisor.
+struct Task {
+   handle: JoinHandle<std::result::Result<(), Aborted>>,
+   abort: AbortHandle,
+
+   /// Set once the supervisor should wind down - each has its own, so that a
+   /// new stream never revives an old one.  The writer has none, it ends with
+   /// its connection.
+   shutdown: Option<Arc<Mutex<bool>>>
+}
+impl Task {
+   fn shut_down(&self) {
+      if let Some(shutdown) = &self.shutdown { *shutdown.lock().unwrap() = true; }
+   }
+}
+
 /// Realtime price quote streamer
 ///
 /// To use it:
-/// 1. Create a new streamer with `Streamer::new().await;`
-/// 1. Subscribe to some symbols with `streamer.subscribe(vec!["AAPL"], |quote| /* do something */).await;`
-/// 1. Let the streamer run `streamer.run().await;`
+/// 1. Create a new streamer with `Streamer::new(vec!["AAPL"]);`
+/// 1. Start streaming quotes with `streamer.stream().await;`
+/// 1. Change the symbols while streaming with `streamer.subscribe(vec!["MSFT"]);` and `streamer.unsubscribe(vec!["AAPL"]);`
+/// 1. Close the connection with `streamer.stop().await;` - or by dropping the stream
+///
+/// A streamer runs one stream at a time - starting another stops the current one.
 pub struct Streamer {
-   subs: Vec<String>,
-   shutdown: Arc<Mutex<bool>>
+   /// `None` streams with the client shared by the free functions
+   client: Option<YahooClient>,
+   subs: Arc<Mutex<Vec<String>>>,
+   sender: Arc<Mutex<Option<UnboundedSender<Message>>>>,
+   task: Mutex<Option<Task>>
 }
 impl Streamer {
    pub fn new(symbols: Vec<&str>) -> Streamer {
-      let mut subs = Vec::new();
-      for symbol in &symbols { subs.push(symbol.to_string()); }
+      Streamer::with_client(None, symbols)
+   }
+
+   fn with_client(client: Option<YahooClient>, symbols: Vec<&str>) -> Streamer {
+      let mut subs: Vec<String> = Vec::new();
+      for symbol in &symbols {
+         if !subs.iter().any(|sub| sub == symbol) { subs.push(symbol.to_string()); }
+      }
+
+      Streamer {
+         client,
+         subs: Arc::new(Mutex::new(subs)),
+         sender: Arc::new(Mutex::new(None)),
+         task: Mutex::new(None)
+      }
+   }
+
+   /// The client to stream with - creating the shared one can fail, so that
+   /// happens when streaming starts rather than in `Streamer::new`
+   fn client(&self) -> Result<&YahooClient> {
+      match &self.client {
+         Some(client) => Ok(client),
+         None => YahooClient::shared()
+      }
+   }
+
+   /// The symbols currently subscribed to
+   pub fn subscriptions(&self) -> Vec<String> {
+      self.subs.lock().unwrap().clone()
+   }
+
+   /// Adds symbols to the subscriptions - when streaming, Yahoo! starts sending
+   /// quotes for them over the current connection.
+   pub fn subscribe(&self, symbols: Vec<&str>) {
+      let added = {
+         let mut subs = self.subs.lock().unwrap();
+         let mut added = Vec::new();
+         for symbol in symbols {
+            if !subs.iter().any(|sub| sub == symbol) {
+               subs.push(symbol.to_string());
+               added.push(symbol.to_string());
+            }
+         }
+         added
+      };
 
-      Streamer { subs, shutdown: Arc::new(Mutex::new(false)) }
+      if !added.is_empty() { self.send(&Subs::Subscribe(added)); }
    }
 
-   pub async fn stream(&self) -> impl Stream<Item = Quote> {
-      let (tx, rx) = mpsc::channel();
+   /// Removes symbols from the subscriptions - when streaming, Yahoo! stops
+   /// sending quotes for them.
+   pub fn unsubscribe(&self, symbols: Vec<&str>) {
+      let removed = {
+         let mut subs = self.subs.lock().unwrap();
+         let removed = subs.iter().filter(|sub| symbols.contains(&sub.as_str())).cloned().collect::<Vec<_>>();
+         subs.retain(|sub| !removed.contains(sub));
+         removed
+      };
 
-      let (stream, _) = connect_async("wss://streamer.finance.yahoo.com").await.unwrap();
+      if !removed.is_empty() { self.send(&Subs::Unsubscribe(removed)); }
+   }
+
+   /// Runs the task behind a stream - an earlier task that is somehow still
+   /// around is shut down and aborted rather than left running.
+   fn spawn(&self, shutdown: Option<Arc<Mutex<bool>>>, task: impl Future<Output = ()> + Send + 'static) {
+      let (task, abort) = future::abortable(task);
+      let task = Task { handle: tokio::spawn(task), abort, shutdown };
+
+      if let Some(old) = self.task.lock().unwrap().replace(task) {
+         old.shut_down();
+         old.abort.abort();
+      }
+   }
+
+   /// Sends a message over the live connection, if there is one.
+   fn send(&self, subs: &Subs) {
+      if let (Some(tx), Ok(message)) = (self.sender.lock().unwrap().as_ref(), serde_json::to_string(subs)) {
+         // a closed connection has nothing to update - the subscriptions go out
+         // in full with the next connection
+         let _ = tx.unbounded_send(Message::Text(message));
+      }
+   }
+
+   /// Streams price quotes for the symbols - stopping any earlier stream first.
+   ///
+   /// Failing to connect fails the call.  Once connected, frames that cannot be
+   /// read come through the stream as errors and can be skipped.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use futures::{ future, StreamExt };
+   /// use yahoo_finance::Streamer;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL", "QQQ"]);
+   ///
+   ///    streamer.stream().await.unwrap()
+   ///       .for_each(|quote| {
+   ///          match quote {
+   ///             Ok(quote) => println!("{} is trading for ${}", quote.symbol, quote.price),
+   ///             Err(err) => eprintln!("skipping a bad quote - {}", err)
+   ///          }
+   ///          future::ready(())
+   ///       })
+   ///       .await;
+   /// }
+   /// ```
+   pub async fn stream(&self) -> Result<impl Stream<Item = Result<Quote>>> {
+      Ok(self.stream_quotes().await?.map(|quote| quote.map(Quote::from)))
+   }
+
+   /// Streams everything Yahoo! sends about the symbols - ie. bid / ask & change.
+   pub async fn stream_quotes(&self) -> Result<impl Stream<Item = Result<StreamQuote>>> {
+      self.stop().await;
+      let (tx, mut rx) = unbounded();
+
+      let request = request(self.client()?).context(error::StreamFailed)?;
+      let (stream, _) = connect_async(request).await.context(error::StreamFailed)?;
       let (mut sink, source) = stream.split();
 
-      // send the symbols we are interested in streaming
-      let message = serde_json::to_string(&Subs { subscribe: self.subs.clone() }).unwrap();
-      tx.send(Message::Text(message)).unwrap();
-
-      // spawn a separate thread for sending out messages
-      let shutdown = self.shutdown.clone();
-      tokio::spawn(async move {
-         loop {
-            // stop on shutdown notification
-            if *(shutdown.lock().unwrap()) { break; }
-
-            // we're still running - so get a message and send it out.
-            // TODO - change this to WAIT on receive so that we don't block shutdown
-            let msg = rx.recv().unwrap();
-            sink.send(msg).await.unwrap();
+      // send the symbols we are interested in streaming - holding on to them until
+      // the sender is in place so that no subscription changes are missed
+      {
+         let subs = self.subs.lock().unwrap();
+         let message = serde_json::to_string(&Subs::Subscribe(subs.clone())).context(error::BadData)?;
+         let _ = tx.unbounded_send(Message::Text(message));
+         *self.sender.lock().unwrap() = Some(tx.clone());
+      }
+
+      // spawn a separate task for sending out messages - it ends once the
+      // connection is closed
+      self.spawn(None, async move {
+         while let Some(msg) = rx.next().await {
+            // the reader finds out about a broken connection
+            let closing = matches!(msg, Message::Close(_));
+            if sink.send(msg).await.is_err() || closing { break; }
          }
       });
 
-      let pong_tx = tx.clone();
-      let shutdown = self.shutdown.clone();
-      source
-         .filter_map(move |msg| {
-            match msg.unwrap() {
-               Message::Ping(_) => { pong_tx.send(Message::Pong("pong".as_bytes().to_vec())).unwrap(); },
-               Message::Close(_) => { *(shutdown.lock().unwrap()) = true; },
-               Message::Text(value) => { return future::ready(Some(value)); },
-               Message::Binary(value) => { return future::ready(Some(String::from_utf8(value).unwrap())); },
-               _ => {}
-            };
-            return future::ready(None)
-         })
-         .map(move |msg| {
-            let data = parse_from_bytes::<PricingData>(&decode(msg).unwrap()).unwrap();
-
-            Quote {
-               symbol: data.id.to_string(),
-               timestamp: data.time as i64,
-               session: convert_session(data.marketHours),
-               price: data.price as f64,
-               volume: data.dayVolume as u64
-            }
-         })
+      let closer = Closer(tx);
+      Ok(source.filter_map(move |msg| {
+         let quote = match msg {
+            Err(err) => Some(Err::<StreamQuote, _>(err).context(error::StreamFailed).map_err(Into::into)),
+            Ok(Message::Ping(data)) => { let _ = closer.0.unbounded_send(Message::Pong(data)); None },
+            Ok(Message::Close(_)) => {
+               // Yahoo! is closing the connection - so the writer is done
+               let _ = closer.0.unbounded_send(Message::Close(None));
+               None
+            },
+            Ok(Message::Text(value)) => Some(parse_quote(value.as_bytes())),
+            Ok(Message::Binary(value)) => Some(parse_quote(&value)),
+            _ => None
+         };
+         future::ready(quote)
+      }))
    }
 
-   pub fn stop(&mut self) {
-      let mut shutdown = self.shutdown.lock().unwrap();
-      *shutdown = true;
+   /// Streams everything Yahoo! sends about the symbols, along with changes to
+   /// the connection.
+   ///
+   /// When the connection drops - or goes stale - the streamer reconnects with
+   /// backoff and resubscribes to the current symbols.  The stream ends when the
+   /// streamer is stopped, when another stream is started or when it runs out
+   /// of reconnect attempts.  Dropping the stream closes the connection.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use futures::{ future, StreamExt };
+   /// use yahoo_finance::{ ConnectionState, ReconnectPolicy, StreamEvent, Streamer };
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL", "QQQ"]);
+   ///
+   ///    streamer.stream_resilient(ReconnectPolicy::default()).await
+   ///       .for_each(|event| {
+   ///          match event {
+   ///             StreamEvent::Quote(quote) => println!("{} is trading for ${}", quote.symbol, quote.price),
+   ///             StreamEvent::State(ConnectionState::Reconnecting { attempt, .. }) => println!("reconnecting ({})", attempt),
+   ///             StreamEvent::State(state) => println!("{:?}", state),
+   ///             StreamEvent::Error(err) => eprintln!("skipping a bad quote - {}", err)
+   ///          }
+   ///          future::ready(())
+   ///       })
+   ///       .await;
+   /// }
+   /// ```
+   pub async fn stream_resilient(&self, policy: ReconnectPolicy) -> impl Stream<Item = StreamEvent> {
+      self.stop().await;
+      let (events, rx) = unbounded();
+
+      // without a client there is nothing to connect with - the stream ends
+      // right away, with nothing to shut down
+      let client = match self.client() {
+         Ok(client) => client.clone(),
+         Err(err) => {
+            let _ = events.unbounded_send(StreamEvent::State(ConnectionState::Disconnected { reason: err.to_string() }));
+            return ResilientStream { events: rx, shutdown: Arc::new(Mutex::new(true)), sender: self.sender.clone() };
+         }
+      };
+
+      let shutdown = Arc::new(Mutex::new(false));
+      let supervisor = Supervisor {
+         client,
+         subs: self.subs.clone(),
+         sender: self.sender.clone(),
+         shutdown: shutdown.clone(),
+         policy,
+         events
+      };
+      self.spawn(Some(shutdown.clone()), supervisor.run());
+
+      ResilientStream { events: rx, shutdown, sender: self.sender.clone() }
    }
-}
\ No newline at end of file
+
+   /// Stops streaming - closing the connection with a Close frame and waiting
+   /// for the streamer's task to finish.
+   pub async fn stop(&self) {
+      let sender = self.sender.lock().unwrap().take();
+      let task = self.task.lock().unwrap().take();
+      if let Some(task) = &task { task.shut_down(); }
+
+      // a live connection closes gracefully - otherwise there's nothing to wait for
+      match sender {
+         Some(tx) if tx.unbounded_send(Message::Close(None)).is_ok() => {},
+         _ => if let Some(task) = &task { task.abort.abort(); }
+      }
+
+      if let Some(task) = task { let _ = task.handle.await; }
+   }
+}
+
+impl YahooClient {
+   /// Creates a realtime price quote streamer for the symbols - see `Streamer`.
+   pub fn streamer(&self, symbols: Vec<&str>) -> Streamer {
+      Streamer::with_client(Some(self.clone()), symbols)
+   }
+}
@@ -0,0 +1,289 @@
+//! A local stand-in for Yahoo's streamer, so that streaming consumers can be
+//! tested without a network connection.
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! use futures::StreamExt;
+//! use yahoo_finance::test_util::{ quote, MockStreamer };
+//! use yahoo_finance::YahooClient;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!    let server = MockStreamer::start().await.unwrap();
+//!    let client = YahooClient::builder().streamer_url(&server.url()).build().unwrap();
+//!    let streamer = client.streamer(vec!["AAPL"]);
+//!
+//!    let mut stream = streamer.stream().await.unwrap();
+//!    server.quote(&quote("AAPL", 112.5));
+//!
+//!    let received = stream.next().await.unwrap().unwrap();
+//!    assert_eq!(112.5, received.price);
+//! }
+//! ```
+use base64::encode;
+use futures::channel::mpsc::{ unbounded, UnboundedReceiver, UnboundedSender };
+use futures::future::{ self, AbortHandle };
+use futures::{ SinkExt, StreamExt };
+use protobuf::Message as _;
+use std::net::SocketAddr;
+use std::sync::{ Arc, Mutex };
+use tokio::net::{ TcpListener, TcpStream };
+use tokio_tungstenite::{ accept_async, tungstenite::protocol::Message, WebSocketStream };
+
+use crate::yahoo::{ PricingData, PricingData_MarketHoursType, PricingData_OptionType, PricingData_QuoteType };
+use crate::{ OptionType, QuoteKind, StreamQuote, TradingSession };
+
+/// Builds a quote for an equity with everything but the symbol and price left
+/// at zero (or empty).  Fill in the rest with struct update syntax - ie.
+/// `StreamQuote { day_volume: 1000, ..quote("AAPL", 112.5) }`.
+pub fn quote(symbol: &str, price: f64) -> StreamQuote {
+   StreamQuote {
+      symbol: symbol.to_string(),
+      kind: QuoteKind::Equity,
+      short_name: String::new(),
+      currency: String::new(),
+      exchange: String::new(),
+      timestamp: 0,
+      session: TradingSession::Regular,
+      price,
+      change: 0.0,
+      change_percent: 0.0,
+      open: 0.0,
+      day_high: 0.0,
+      day_low: 0.0,
+      previous_close: 0.0,
+      day_volume: 0,
+      last_size: 0,
+      bid: 0.0,
+      bid_size: 0,
+      ask: 0.0,
+      ask_size: 0,
+      price_hint: 0,
+      option_type: None,
+      underlying_symbol: String::new(),
+      strike: 0.0,
+      expire_date: 0,
+      open_interest: 0,
+      mini_option: 0,
+      volume_24hr: 0,
+      volume_all_currencies: 0,
+      from_currency: String::new(),
+      last_market: String::new(),
+      circulating_supply: 0.0,
+      market_cap: 0.0
+   }
+}
+
+fn convert_kind(kind: QuoteKind) -> PricingData_QuoteType {
+   match kind {
+      QuoteKind::None => PricingData_QuoteType::NONE,
+      QuoteKind::AltSymbol => PricingData_QuoteType::ALTSYMBOL,
+      QuoteKind::Heartbeat => PricingData_QuoteType::HEARTBEAT,
+      QuoteKind::Equity => PricingData_QuoteType::EQUITY,
+      QuoteKind::Index => PricingData_QuoteType::INDEX,
+      QuoteKind::MutualFund => PricingData_QuoteType::MUTUALFUND,
+      QuoteKind::MoneyMarket => PricingData_QuoteType::MONEYMARKET,
+      QuoteKind::Option => PricingData_QuoteType::OPTION,
+      QuoteKind::Currency => PricingData_QuoteType::CURRENCY,
+      QuoteKind::Warrant => PricingData_QuoteType::WARRANT,
+      QuoteKind::Bond => PricingData_QuoteType::BOND,
+      QuoteKind::Future => PricingData_QuoteType::FUTURE,
+      QuoteKind::Etf => PricingData_QuoteType::ETF,
+      QuoteKind::Commodity => PricingData_QuoteType::COMMODITY,
+      QuoteKind::EcnQuote => PricingData_QuoteType::ECNQUOTE,
+      QuoteKind::Cryptocurrency => PricingData_QuoteType::CRYPTOCURRENCY,
+      QuoteKind::Indicator => PricingData_QuoteType::INDICATOR,
+      QuoteKind::Industry => PricingData_QuoteType::INDUSTRY
+   }
+}
+
+fn convert_session(session: &TradingSession) -> PricingData_MarketHoursType {
+   match session {
+      TradingSession::PreMarket => PricingData_MarketHoursType::PRE_MARKET,
+      TradingSession::Regular => PricingData_MarketHoursType::REGULAR_MARKET,
+      TradingSession::AfterHours => PricingData_MarketHoursType::POST_MARKET,
+      _ => PricingData_MarketHoursType::EXTENDED_HOURS_MARKET
+   }
+}
+
+/// Encodes a quote the way Yahoo! does - base64 encoded protobuf `PricingData`.
+pub fn encode_quote(quote: &StreamQuote) -> String {
+   let mut data = PricingData::new();
+   data.id = quote.symbol.clone();
+   data.quoteType = convert_kind(quote.kind);
+   data.shortName = quote.short_name.clone();
+   data.currency = quote.currency.clone();
+   data.exchange = quote.exchange.clone();
+   data.time = quote.timestamp;
+   data.marketHours = convert_session(&quote.session);
+   data.price = quote.price as f32;
+   data.change = quote.change as f32;
+   data.changePercent = quote.change_percent as f32;
+   data.openPrice = quote.open as f32;
+   data.dayHigh = quote.day_high as f32;
+   data.dayLow = quote.day_low as f32;
+   data.previousClose = quote.previous_close as f32;
+   data.dayVolume = quote.day_volume;
+   data.lastSize = quote.last_size;
+   data.bid = quote.bid as f32;
+   data.bidSize = quote.bid_size;
+   data.ask = quote.ask as f32;
+   data.askSize = quote.ask_size;
+   data.priceHint = quote.price_hint;
+   data.optionsType = match quote.option_type {
+      Some(OptionType::Put) => PricingData_OptionType::PUT,
+      _ => PricingData_OptionType::CALL
+   };
+   data.underlyingSymbol = quote.underlying_symbol.clone();
+   data.strikePrice = quote.strike as f32;
+   data.expireDate = quote.expire_date;
+   data.openInterest = quote.open_interest;
+   data.miniOption = quote.mini_option;
+   data.vol_24hr = quote.volume_24hr;
+   data.volAllCurrencies = quote.volume_all_currencies;
+   data.fromcurrency = quote.from_currency.clone();
+   data.lastMarket = quote.last_market.clone();
+   data.circulatingSupply = quote.circulating_supply;
+   data.marketcap = quote.market_cap;
+
+   // every field is plain data, so encoding can't fail
+   encode(&data.write_to_bytes().expect("PricingData failed to encode"))
+}
+
+/// What the test wants the server to do next.
+enum Command {
+   Send(Message),
+   Disconnect
+}
+
+/// What the server has seen from its clients.
+#[derive(Default)]
+struct Seen {
+   connections: usize,
+   received: Vec<String>,
+   pongs: usize,
+   closes: usize
+}
+
+/// A local websocket server that streams like Yahoo! - point a client at it
+/// with `YahooClientBuilder::streamer_url(&server.url())`.
+///
+/// The server talks to one client at a time.  Frames are queued up until a
+/// client connects, then go out in order - and anything left over when a
+/// client goes away goes to the next one.  The server shuts down when dropped.
+pub struct MockStreamer {
+   addr: SocketAddr,
+   commands: UnboundedSender<Command>,
+   seen: Arc<Mutex<Seen>>,
+   abort: AbortHandle
+}
+impl MockStreamer {
+   /// Starts the server on a free local port.
+   pub async fn start() -> std::io::Result<MockStreamer> {
+      let mut listener = TcpListener::bind("127.0.0.1:0").await?;
+      let addr = listener.local_addr()?;
+
+      let (commands, mut rx) = unbounded();
+      let seen = Arc::new(Mutex::new(Seen::default()));
+
+      let server_seen = seen.clone();
+      let (server, abort) = future::abortable(async move {
+         while let Ok((socket, _)) = listener.accept().await {
+            if let Ok(socket) = accept_async(socket).await {
+               server_seen.lock().unwrap().connections += 1;
+               serve(socket, &mut rx, &server_seen).await;
+            }
+         }
+      });
+      tokio::spawn(server);
+
+      Ok(MockStreamer { addr, commands, seen, abort })
+   }
+
+   /// The websocket URL of the server - ie. `ws://127.0.0.1:4321/`
+   pub fn url(&self) -> String {
+      format!("ws://{}/", self.addr)
+   }
+
+   /// Streams a quote, encoded the way Yahoo! does.
+   pub fn quote(&self, quote: &StreamQuote) {
+      self.send(Message::Text(encode_quote(quote)));
+   }
+
+   /// Sends a text frame as is - ie. to test frames that can't be decoded.
+   pub fn text(&self, text: &str) {
+      self.send(Message::Text(text.to_string()));
+   }
+
+   /// Pings the client - see `pongs` for the replies.
+   pub fn ping(&self) {
+      self.send(Message::Ping(Vec::new()));
+   }
+
+   /// Closes the connection gracefully with a Close frame.
+   pub fn close(&self) {
+      self.send(Message::Close(None));
+   }
+
+   /// Drops the connection without a Close frame - as a network failure would.
+   pub fn disconnect(&self) {
+      let _ = self.commands.unbounded_send(Command::Disconnect);
+   }
+
+   /// How many clients have connected so far
+   pub fn connections(&self) -> usize {
+      self.seen.lock().unwrap().connections
+   }
+
+   /// The text frames received from clients, across every connection - ie.
+   /// `{"subscribe":["AAPL"]}`
+   pub fn received(&self) -> Vec<String> {
+      self.seen.lock().unwrap().received.clone()
+   }
+
+   /// How many pongs clients have replied with
+   pub fn pongs(&self) -> usize {
+      self.seen.lock().unwrap().pongs
+   }
+
+   /// How many clients have closed their connection with a Close frame
+   pub fn closes(&self) -> usize {
+      self.seen.lock().unwrap().closes
+   }
+
+   fn send(&self, msg: Message) {
+      let _ = self.commands.unbounded_send(Command::Send(msg));
+   }
+}
+impl Drop for MockStreamer {
+   fn drop(&mut self) {
+      self.abort.abort();
+   }
+}
+
+/// Talks to a single client until either side ends the connection.
+async fn serve(socket: WebSocketStream<TcpStream>, commands: &mut UnboundedReceiver<Command>, seen: &Arc<Mutex<Seen>>) {
+   let (mut sink, mut source) = socket.split();
+
+   loop {
+      tokio::select! {
+         msg = source.next() => match msg {
+            Some(Ok(Message::Text(text))) => seen.lock().unwrap().received.push(text),
+            Some(Ok(Message::Pong(_))) => seen.lock().unwrap().pongs += 1,
+            Some(Ok(Message::Close(_))) => {
+               seen.lock().unwrap().closes += 1;
+               return;
+            },
+            Some(Err(_)) | None => return,
+            Some(Ok(_)) => {}
+         },
+         Some(command) = commands.next() => match command {
+            // after a close, keep reading until the client acknowledges it
+            Command::Send(msg) => if sink.send(msg).await.is_err() { return; },
+            Command::Disconnect => return
+         },
+         else => return
+      }
+   }
+}
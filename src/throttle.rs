@@ -0,0 +1,127 @@
+use rand::Rng;
+use reqwest::header::RETRY_AFTER;
+use reqwest::{Response, StatusCode};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::time::delay_for;
+
+/// How calls to Yahoo! are retried when Yahoo! is throttling us (429) or is
+/// having problems of its own (5xx).
+///
+/// Retries back off exponentially from the base delay - with some random jitter
+/// so that concurrent calls don't all retry at once - unless Yahoo! tells us how
+/// long to wait with a `Retry-After` header.  Either way, we never wait longer
+/// than the maximum delay.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use yahoo_finance::{ RetryPolicy, YahooClient };
+///
+/// let client = YahooClient::builder()
+///    .retry(RetryPolicy { max_retries: 5, base_delay: Duration::from_secs(1), max_delay: Duration::from_secs(60) })
+///    .build()
+///    .unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+   /// The most times a single call is retried - 0 turns off retries
+   pub max_retries: u32,
+
+   /// The delay before the first retry
+   pub base_delay: Duration,
+
+   /// The longest we'll wait between retries
+   pub max_delay: Duration
+}
+impl RetryPolicy {
+   /// Never retry - fail on the first error
+   pub fn none() -> RetryPolicy {
+      RetryPolicy { max_retries: 0, ..RetryPolicy::default() }
+   }
+
+   /// Whether a response is worth retrying
+   pub(crate) fn should_retry(&self, response: &Response, attempt: u32) -> bool {
+      let status = response.status();
+      attempt < self.max_retries && (status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error())
+   }
+
+   /// How long to wait before retrying a response
+   pub(crate) fn delay(&self, response: &Response, attempt: u32) -> Duration {
+      let retry_after = response.headers().get(RETRY_AFTER)
+         .and_then(|value| value.to_str().ok())
+         .and_then(|value| parse_retry_after(value.trim()));
+      if let Some(delay) = retry_after { return delay.min(self.max_delay); }
+
+      backoff(self.base_delay, self.max_delay, attempt)
+   }
+}
+impl Default for RetryPolicy {
+   /// Retry 3 times, starting at half a second
+   fn default() -> RetryPolicy {
+      RetryPolicy { max_retries: 3, base_delay: Duration::from_millis(500), max_delay: Duration::from_secs(30) }
+   }
+}
+
+/// Reads a `Retry-After` header - either a number of seconds or the HTTP date
+/// to retry after.
+fn parse_retry_after(value: &str) -> Option<Duration> {
+   if let Ok(seconds) = value.parse::<u64>() { return Some(Duration::from_secs(seconds)); }
+
+   // a date that has already passed means there's no need to wait
+   let date = httpdate::parse_http_date(value).ok()?;
+   Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
+
+/// Backs off exponentially from the base delay, with the second half of the
+/// delay randomized.
+pub(crate) fn backoff(base_delay: Duration, max_delay: Duration, attempt: u32) -> Duration {
+   let ceiling = 2u32.checked_pow(attempt)
+      .and_then(|factor| base_delay.checked_mul(factor))
+      .map_or(max_delay, |delay| delay.min(max_delay));
+   ceiling / 2 + ceiling.mul_f64(rand::thread_rng().gen::<f64>() / 2.0)
+}
+
+#[derive(Debug)]
+struct Bucket {
+   tokens: f64,
+   updated: Instant
+}
+
+/// A token bucket shared by every call made with a client.
+#[derive(Debug, Clone)]
+pub(crate) struct RateLimiter {
+   rate: f64,
+   capacity: f64,
+   bucket: Arc<Mutex<Bucket>>
+}
+impl RateLimiter {
+   pub fn new(per_second: f64, burst: u32) -> RateLimiter {
+      let capacity = f64::from(burst.max(1));
+      RateLimiter { rate: per_second, capacity, bucket: Arc::new(Mutex::new(Bucket { tokens: capacity, updated: Instant::now() })) }
+   }
+
+   /// Waits until the bucket has a token for another call
+   pub async fn acquire(&self) {
+      loop {
+         let wait = {
+            let mut bucket = self.bucket.lock().unwrap();
+
+            // top up the bucket for the time that has passed
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+            bucket.updated = now;
+
+            if bucket.tokens >= 1.0 {
+               bucket.tokens -= 1.0;
+               return;
+            }
+            Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate)
+         };
+
+         delay_for(wait).await;
+      }
+   }
+}
@@ -1,32 +1,53 @@
-use chrono::serde::ts_seconds;
+use chrono::serde::{ ts_seconds, ts_seconds_option };
 use chrono::{DateTime, Utc};
 use reqwest::Url;
 use serde::Deserialize;
 use snafu::{ ensure, OptionExt, ResultExt };
-use std::env;
+use std::collections::HashMap;
 
-use crate::{error, Interval, Result};
-
-const BASE_URL: &'static str = "https://query1.finance.yahoo.com/v8/finance/chart/";
+use crate::{error, Interval, Result, YahooClient};
 
 /// Helper function to build up the main query URL
-fn build_query(symbol: &str) -> Result<Url> {
-   let base = env::var("TEST_URL").unwrap_or(BASE_URL.to_string());
-   Ok(Url::parse(&base).context(error::InternalURL { url: &base })?
-      .join(symbol).context(error::InternalURL { url: symbol })?)
+fn build_query(client: &YahooClient, symbol: &str) -> Result<Url> {
+   Ok(client.chart_url().join(symbol).context(error::InternalURL { url: symbol })?)
 }
 
+ez_serde!(TradingPeriod {
+   timezone: String,
+
+   #[serde(with = "ts_seconds")]
+   start: DateTime<Utc>,
+
+   #[serde(with = "ts_seconds")]
+   end: DateTime<Utc>,
+
+   gmtoffset: i32
+});
+
+ez_serde!(TradingPeriods { pre: TradingPeriod, regular: TradingPeriod, post: TradingPeriod });
+
 ez_serde!(Meta {
    symbol: String,
+   currency: Option<String>,
+   exchange_name: Option<String>,
+   instrument_type: Option<String>,
 
-   #[serde(with = "ts_seconds")]
-   first_trade_date: DateTime<Utc>,
+   #[serde(with = "ts_seconds_option", default)]
+   first_trade_date: Option<DateTime<Utc>>,
 
    #[serde(rename = "regularMarketPrice")]
-   current_price: f32,
+   current_price: f64,
 
    #[serde(rename = "chartPreviousClose")]
-   previous_close: f32
+   previous_close: Option<f64>,
+
+   gmtoffset: Option<i32>,
+   exchange_timezone_name: Option<String>,
+   price_hint: Option<u32>,
+   current_trading_period: Option<TradingPeriods>,
+
+   #[serde(default)]
+   valid_ranges: Vec<String>
 });
 
 ez_serde!(OHLCV {
@@ -46,7 +67,27 @@ ez_serde!(OHLCV {
    volumes: Vec<Option<u64>>
 });
 
-ez_serde!(Indicators { #[serde(rename = "quote", default)] quotes: Vec<OHLCV> });
+ez_serde!(AdjClose { #[serde(rename = "adjclose", default)] adjcloses: Vec<Option<f64>> });
+
+ez_serde!(Indicators {
+   #[serde(rename = "quote", default)]
+   quotes: Vec<OHLCV>,
+
+   #[serde(rename = "adjclose", default)]
+   adjcloses: Vec<AdjClose>
+});
+
+ez_serde!(DividendEvent { amount: f64, date: i64 });
+
+ez_serde!(SplitEvent { date: i64, numerator: f64, denominator: f64 });
+
+ez_serde!(Events {
+   #[serde(default)]
+   dividends: HashMap<String, DividendEvent>,
+
+   #[serde(default)]
+   splits: HashMap<String, SplitEvent>
+});
 
 ez_serde!(Data {
    meta: Meta,
@@ -54,21 +95,17 @@ ez_serde!(Data {
    #[serde(rename = "timestamp", default)]
    timestamps: Vec<i64>,
 
-   indicators: Indicators
+   indicators: Indicators,
+
+   events: Option<Events>
 });
 
 ez_serde!(Error {code: String, description: String });
 ez_serde!(Chart { result: Option<Vec<Data>>, error: Option<Error> });
 ez_serde!(Response { chart: Chart });
 
-async fn load(url: &Url) -> Result<Data> {
-   // make the call - we do not really expect this to fail.
-   // ie - we won't 404 if the symbol doesn't exist
-   let response = reqwest::get(url.clone()).await.context(error::RequestFailed)?;
-   ensure!(
-      response.status().is_success(),
-      error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
-   );
+async fn load(client: &YahooClient, url: &Url) -> Result<Data> {
+   let response = client.get(url).await?;
 
    let data = response.text().await.context(error::UnexpectedErrorRead { url: url.to_string() })?;
    let chart = serde_json::from_str::<Response>(&data).context(error::BadData)?.chart;
@@ -85,21 +122,45 @@ async fn load(url: &Url) -> Result<Data> {
    Ok(result[0].clone())
 }
 
-pub async fn load_daily(symbol: &str, period: Interval) -> Result<Data> {
-   let mut lookup = build_query(symbol)?;
+pub async fn load_daily(client: &YahooClient, symbol: &str, period: &Interval) -> Result<Data> {
+   let mut lookup = build_query(client, symbol)?;
    lookup.query_pairs_mut()
       .append_pair("range", &period.to_string())
       .append_pair("interval", "1d");
 
-   load(&lookup).await
+   load(client, &lookup).await
+}
+
+pub async fn load_daily_range(client: &YahooClient, symbol: &str, start: i64, end: i64) -> Result<Data> {
+   load_range(client, symbol, start, end, "1d").await
 }
 
-pub async fn load_daily_range(symbol: &str, start: i64, end: i64) -> Result<Data> {
-   let mut lookup = build_query(symbol)?;
+pub async fn load_range(client: &YahooClient, symbol: &str, start: i64, end: i64, granularity: &str) -> Result<Data> {
+   let mut lookup = build_query(client, symbol)?;
    lookup.query_pairs_mut()
       .append_pair("period1", &start.to_string())
       .append_pair("period2", &end.to_string())
-      .append_pair("interval", "1d");
+      .append_pair("interval", granularity);
+
+   load(client, &lookup).await
+}
+
+pub async fn load_intraday(client: &YahooClient, symbol: &str, period: Interval, granularity: &str) -> Result<Data> {
+   let mut lookup = build_query(client, symbol)?;
+   lookup.query_pairs_mut()
+      .append_pair("range", &period.to_string())
+      .append_pair("interval", granularity);
+
+   load(client, &lookup).await
+}
+
+pub async fn load_events(client: &YahooClient, symbol: &str, start: i64, end: i64) -> Result<Data> {
+   let mut lookup = build_query(client, symbol)?;
+   lookup.query_pairs_mut()
+      .append_pair("period1", &start.to_string())
+      .append_pair("period2", &end.to_string())
+      .append_pair("interval", "1d")
+      .append_pair("events", "div,split");
 
-   load(&lookup).await
+   load(client, &lookup).await
 }
@@ -1,8 +1,24 @@
 mod chart;
-pub use chart::{load_daily, load_daily_range, Data};
+pub use chart::{load_daily, load_daily_range, load_events, load_intraday, load_range, Data, Meta, TradingPeriod};
+
+mod options;
+pub use options::{load_options, ContractData, OptionsData};
+
+mod quote;
+pub use quote::{load_quotes, QuoteData};
+
+mod quote_summary;
+pub use quote_summary::{load_summary, QuoteSummaryData};
 
 mod realtime;
-pub use realtime::{PricingData, PricingData_MarketHoursType};
+pub use realtime::{PricingData, PricingData_MarketHoursType, PricingData_OptionType, PricingData_QuoteType};
+
+mod summary;
+pub use summary::{
+   raw_date, raw_number, BalanceSheetData, CashFlowData, DefaultKeyStatistics, EarningsCalendarData, EarningsResultData,
+   EarningsTrendData, EstimateData, FinancialData, GradeChangeData, IncomeStatementData, InsiderHolderData, InsiderTransactionData,
+   MajorHoldersBreakdown, OwnershipData, RawNumber, RecommendationTrendData, SummaryDetail, TopHoldings
+};
 
 mod web_scraper;
 pub use web_scraper::{scrape, QuoteSummaryStore, CompanyProfile};
@@ -0,0 +1,68 @@
+use reqwest::Url;
+use serde::Deserialize;
+use snafu::{ ensure, OptionExt, ResultExt };
+
+use crate::{ error, Result, YahooClient };
+
+ez_serde!(ContractData {
+   contract_symbol: String,
+   strike: f64,
+   currency: Option<String>,
+   last_price: Option<f64>,
+   change: Option<f64>,
+   percent_change: Option<f64>,
+   volume: Option<u64>,
+   open_interest: Option<u64>,
+   bid: Option<f64>,
+   ask: Option<f64>,
+   expiration: i64,
+   last_trade_date: Option<i64>,
+   implied_volatility: Option<f64>,
+
+   #[serde(default)]
+   in_the_money: bool
+});
+
+ez_serde!(Options {
+   expiration_date: i64,
+
+   #[serde(default)]
+   calls: Vec<ContractData>,
+
+   #[serde(default)]
+   puts: Vec<ContractData>
+});
+
+ez_serde!(OptionsData {
+   underlying_symbol: String,
+
+   #[serde(default)]
+   expiration_dates: Vec<i64>,
+
+   #[serde(default)]
+   options: Vec<Options>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(OptionChain { result: Option<Vec<OptionsData>>, error: Option<Error> });
+ez_serde!(Response { option_chain: OptionChain });
+
+/// Loads the options for a symbol - for the nearest expiration unless one is given
+pub async fn load_options(client: &YahooClient, symbol: &str, expiration: Option<i64>) -> Result<OptionsData> {
+   let mut url: Url = client.options_url().join(symbol).context(error::InternalURL { url: symbol })?;
+   if let Some(expiration) = expiration {
+      url.query_pairs_mut().append_pair("date", &expiration.to_string());
+   }
+
+   let response = client.get(&url).await?;
+   let data = response.text().await.context(error::UnexpectedErrorRead { url: url.to_string() })?;
+   let chain = serde_json::from_str::<Response>(&data).context(error::BadData)?.option_chain;
+
+   if let Some(err) = chain.error {
+      error::OptionsFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let mut result = chain.result.context(error::UnexpectedErrorYahoo)?;
+   ensure!(!result.is_empty(), error::UnexpectedErrorYahoo);
+   Ok(result.swap_remove(0))
+}
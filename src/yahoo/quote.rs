@@ -0,0 +1,73 @@
+use reqwest::Url;
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Result, YahooClient };
+
+ez_serde!(QuoteData {
+   symbol: String,
+   short_name: Option<String>,
+   long_name: Option<String>,
+   currency: Option<String>,
+   exchange: Option<String>,
+   quote_type: Option<String>,
+   market_state: Option<String>,
+
+   regular_market_time: Option<i64>,
+   regular_market_price: Option<f64>,
+   regular_market_change: Option<f64>,
+   regular_market_change_percent: Option<f64>,
+   regular_market_open: Option<f64>,
+   regular_market_day_high: Option<f64>,
+   regular_market_day_low: Option<f64>,
+   regular_market_previous_close: Option<f64>,
+   regular_market_volume: Option<u64>,
+
+   #[serde(rename = "averageDailyVolume3Month")]
+   average_volume: Option<u64>,
+
+   bid: Option<f64>,
+   bid_size: Option<u64>,
+   ask: Option<f64>,
+   ask_size: Option<u64>,
+
+   fifty_two_week_low: Option<f64>,
+   fifty_two_week_high: Option<f64>,
+
+   market_cap: Option<u64>,
+
+   #[serde(rename = "trailingPE")]
+   trailing_pe: Option<f64>,
+
+   #[serde(rename = "forwardPE")]
+   forward_pe: Option<f64>,
+
+   pre_market_time: Option<i64>,
+   pre_market_price: Option<f64>,
+   pre_market_change: Option<f64>,
+   pre_market_change_percent: Option<f64>,
+
+   post_market_time: Option<i64>,
+   post_market_price: Option<f64>,
+   post_market_change: Option<f64>,
+   post_market_change_percent: Option<f64>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(QuoteResponse { result: Option<Vec<QuoteData>>, error: Option<Error> });
+ez_serde!(Response { quote_response: QuoteResponse });
+
+pub async fn load_quotes(client: &YahooClient, symbols: &[&str]) -> Result<Vec<QuoteData>> {
+   let mut url: Url = client.quote_url().clone();
+   url.query_pairs_mut().append_pair("symbols", &symbols.join(","));
+
+   let response = client.get(&url).await?;
+   let data = response.text().await.context(error::UnexpectedErrorRead { url: url.to_string() })?;
+   let quotes = serde_json::from_str::<Response>(&data).context(error::BadData)?.quote_response;
+
+   if let Some(err) = quotes.error {
+      error::QuoteFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   quotes.result.context(error::UnexpectedErrorYahoo).map_err(Into::into)
+}
@@ -0,0 +1,68 @@
+use reqwest::Url;
+use serde::Deserialize;
+use snafu::{ ensure, OptionExt, ResultExt };
+
+use crate::{ error, Result, YahooClient };
+use super::summary::{
+   BalanceSheetHistory, CalendarEvents, CashFlowHistory, DefaultKeyStatistics, EarningsHistory, EarningsTrend, FinancialData,
+   IncomeStatementHistory, InsiderHolders, InsiderTransactions, MajorHoldersBreakdown, Ownership, RecommendationTrend,
+   SummaryDetail, UpgradeDowngradeHistory
+};
+
+// Yahoo! only sends the modules that were asked for - and that it has for the
+// symbol - so every module is optional.
+ez_serde!(QuoteSummaryData {
+   income_statement_history: Option<IncomeStatementHistory>,
+   income_statement_history_quarterly: Option<IncomeStatementHistory>,
+   balance_sheet_history: Option<BalanceSheetHistory>,
+   balance_sheet_history_quarterly: Option<BalanceSheetHistory>,
+
+   #[serde(rename = "cashflowStatementHistory")]
+   cash_flow_history: Option<CashFlowHistory>,
+
+   #[serde(rename = "cashflowStatementHistoryQuarterly")]
+   cash_flow_history_quarterly: Option<CashFlowHistory>,
+
+   earnings_history: Option<EarningsHistory>,
+   earnings_trend: Option<EarningsTrend>,
+   calendar_events: Option<CalendarEvents>,
+
+   recommendation_trend: Option<RecommendationTrend>,
+   upgrade_downgrade_history: Option<UpgradeDowngradeHistory>,
+   financial_data: Option<FinancialData>,
+
+   major_holders_breakdown: Option<MajorHoldersBreakdown>,
+   institution_ownership: Option<Ownership>,
+   fund_ownership: Option<Ownership>,
+   insider_transactions: Option<InsiderTransactions>,
+   insider_holders: Option<InsiderHolders>,
+
+   default_key_statistics: Option<DefaultKeyStatistics>,
+   summary_detail: Option<SummaryDetail>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(QuoteSummary { result: Option<Vec<QuoteSummaryData>>, error: Option<Error> });
+ez_serde!(Response { quote_summary: QuoteSummary });
+
+/// Loads quote summary modules for a symbol - ie. `incomeStatementHistory`
+///
+/// Yahoo! sometimes leaves out what identifies a row (ie. the period of an
+/// estimate or the name of a holder) - the loaders skip those rows rather than
+/// failing the whole call.
+pub async fn load_summary(client: &YahooClient, symbol: &str, modules: &[&str]) -> Result<QuoteSummaryData> {
+   let mut url: Url = client.quote_summary_url().join(symbol).context(error::InternalURL { url: symbol })?;
+   url.query_pairs_mut().append_pair("modules", &modules.join(","));
+
+   let response = client.get(&url).await?;
+   let data = response.text().await.context(error::UnexpectedErrorRead { url: url.to_string() })?;
+   let summary = serde_json::from_str::<Response>(&data).context(error::BadData)?.quote_summary;
+
+   if let Some(err) = summary.error {
+      error::SummaryFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let mut result = summary.result.context(error::UnexpectedErrorYahoo)?;
+   ensure!(!result.is_empty(), error::UnexpectedErrorYahoo);
+   Ok(result.swap_remove(0))
+}
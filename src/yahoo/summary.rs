@@ -0,0 +1,308 @@
+use chrono::{ DateTime, TimeZone, Utc };
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// Yahoo! wraps most quote summary values as `{"raw": 123.4, "fmt": "123.40"}` -
+// or `{}` when it doesn't have the value - so only the raw value is kept.
+ez_serde!(RawNumber { raw: Option<f64> });
+ez_serde!(RawDate { raw: Option<i64> });
+
+pub fn raw_number(value: Option<RawNumber>) -> Option<f64> {
+   value.and_then(|value| value.raw)
+}
+
+pub fn raw_date(value: Option<RawDate>) -> Option<DateTime<Utc>> {
+   value.and_then(|value| value.raw).map(|time| Utc.timestamp(time, 0))
+}
+
+ez_serde!(IncomeStatementData {
+   end_date: Option<RawDate>,
+   total_revenue: Option<RawNumber>,
+   cost_of_revenue: Option<RawNumber>,
+   gross_profit: Option<RawNumber>,
+   research_development: Option<RawNumber>,
+   selling_general_administrative: Option<RawNumber>,
+   total_operating_expenses: Option<RawNumber>,
+   operating_income: Option<RawNumber>,
+   ebit: Option<RawNumber>,
+   interest_expense: Option<RawNumber>,
+   income_before_tax: Option<RawNumber>,
+   income_tax_expense: Option<RawNumber>,
+   net_income: Option<RawNumber>,
+   net_income_applicable_to_common_shares: Option<RawNumber>
+});
+
+ez_serde!(BalanceSheetData {
+   end_date: Option<RawDate>,
+   cash: Option<RawNumber>,
+   short_term_investments: Option<RawNumber>,
+   net_receivables: Option<RawNumber>,
+   inventory: Option<RawNumber>,
+   total_current_assets: Option<RawNumber>,
+   long_term_investments: Option<RawNumber>,
+   property_plant_equipment: Option<RawNumber>,
+
+   #[serde(rename = "goodWill")]
+   goodwill: Option<RawNumber>,
+
+   intangible_assets: Option<RawNumber>,
+   total_assets: Option<RawNumber>,
+   accounts_payable: Option<RawNumber>,
+   short_long_term_debt: Option<RawNumber>,
+   total_current_liabilities: Option<RawNumber>,
+   long_term_debt: Option<RawNumber>,
+
+   #[serde(rename = "totalLiab")]
+   total_liabilities: Option<RawNumber>,
+
+   common_stock: Option<RawNumber>,
+   retained_earnings: Option<RawNumber>,
+   total_stockholder_equity: Option<RawNumber>
+});
+
+ez_serde!(CashFlowData {
+   end_date: Option<RawDate>,
+   net_income: Option<RawNumber>,
+   depreciation: Option<RawNumber>,
+   change_to_account_receivables: Option<RawNumber>,
+   change_to_inventory: Option<RawNumber>,
+   total_cash_from_operating_activities: Option<RawNumber>,
+   capital_expenditures: Option<RawNumber>,
+   investments: Option<RawNumber>,
+   total_cashflows_from_investing_activities: Option<RawNumber>,
+   dividends_paid: Option<RawNumber>,
+   net_borrowings: Option<RawNumber>,
+   repurchase_of_stock: Option<RawNumber>,
+   issuance_of_stock: Option<RawNumber>,
+   total_cash_from_financing_activities: Option<RawNumber>,
+   change_in_cash: Option<RawNumber>
+});
+
+ez_serde!(IncomeStatementHistory {
+   #[serde(default)]
+   income_statement_history: Vec<IncomeStatementData>
+});
+ez_serde!(BalanceSheetHistory {
+   #[serde(default)]
+   balance_sheet_statements: Vec<BalanceSheetData>
+});
+ez_serde!(CashFlowHistory {
+   #[serde(default)]
+   cashflow_statements: Vec<CashFlowData>
+});
+
+ez_serde!(EarningsResultData {
+   quarter: Option<RawDate>,
+   period: Option<String>,
+   eps_actual: Option<RawNumber>,
+   eps_estimate: Option<RawNumber>,
+   eps_difference: Option<RawNumber>,
+   surprise_percent: Option<RawNumber>
+});
+ez_serde!(EarningsHistory {
+   #[serde(default)]
+   history: Vec<EarningsResultData>
+});
+
+ez_serde!(EarningsCalendarData {
+   #[serde(default)]
+   earnings_date: Vec<RawDate>,
+
+   earnings_average: Option<RawNumber>,
+   earnings_low: Option<RawNumber>,
+   earnings_high: Option<RawNumber>,
+   revenue_average: Option<RawNumber>,
+   revenue_low: Option<RawNumber>,
+   revenue_high: Option<RawNumber>
+});
+ez_serde!(CalendarEvents { earnings: Option<EarningsCalendarData> });
+
+ez_serde!(EstimateData {
+   avg: Option<RawNumber>,
+   low: Option<RawNumber>,
+   high: Option<RawNumber>,
+   number_of_analysts: Option<RawNumber>,
+   growth: Option<RawNumber>,
+   year_ago_eps: Option<RawNumber>,
+   year_ago_revenue: Option<RawNumber>
+});
+ez_serde!(EarningsTrendData {
+   period: Option<String>,
+   end_date: Option<String>,
+   earnings_estimate: Option<EstimateData>,
+   revenue_estimate: Option<EstimateData>
+});
+ez_serde!(EarningsTrend {
+   #[serde(default)]
+   trend: Vec<EarningsTrendData>
+});
+
+ez_serde!(RecommendationTrendData {
+   period: Option<String>,
+
+   #[serde(default)]
+   strong_buy: u32,
+
+   #[serde(default)]
+   buy: u32,
+
+   #[serde(default)]
+   hold: u32,
+
+   #[serde(default)]
+   sell: u32,
+
+   #[serde(default)]
+   strong_sell: u32
+});
+ez_serde!(RecommendationTrend {
+   #[serde(default)]
+   trend: Vec<RecommendationTrendData>
+});
+
+ez_serde!(GradeChangeData {
+   epoch_grade_date: Option<i64>,
+   firm: Option<String>,
+   to_grade: Option<String>,
+   from_grade: Option<String>,
+   action: Option<String>
+});
+ez_serde!(UpgradeDowngradeHistory {
+   #[serde(default)]
+   history: Vec<GradeChangeData>
+});
+
+ez_serde!(FinancialData {
+   current_price: Option<RawNumber>,
+   target_high_price: Option<RawNumber>,
+   target_low_price: Option<RawNumber>,
+   target_mean_price: Option<RawNumber>,
+   target_median_price: Option<RawNumber>,
+   recommendation_mean: Option<RawNumber>,
+   recommendation_key: Option<String>,
+   number_of_analyst_opinions: Option<RawNumber>
+});
+
+ez_serde!(MajorHoldersBreakdown {
+   insiders_percent_held: Option<RawNumber>,
+   institutions_percent_held: Option<RawNumber>,
+   institutions_float_percent_held: Option<RawNumber>,
+   institutions_count: Option<RawNumber>
+});
+
+ez_serde!(OwnershipData {
+   organization: Option<String>,
+   report_date: Option<RawDate>,
+   pct_held: Option<RawNumber>,
+   position: Option<RawNumber>,
+   value: Option<RawNumber>
+});
+ez_serde!(Ownership {
+   #[serde(default)]
+   ownership_list: Vec<OwnershipData>
+});
+
+ez_serde!(InsiderTransactionData {
+   filer_name: Option<String>,
+   filer_relation: Option<String>,
+   transaction_text: Option<String>,
+   start_date: Option<RawDate>,
+   shares: Option<RawNumber>,
+   value: Option<RawNumber>,
+   ownership: Option<String>
+});
+ez_serde!(InsiderTransactions {
+   #[serde(default)]
+   transactions: Vec<InsiderTransactionData>
+});
+
+ez_serde!(InsiderHolderData {
+   name: Option<String>,
+   relation: Option<String>,
+   transaction_description: Option<String>,
+   latest_trans_date: Option<RawDate>,
+   position_direct: Option<RawNumber>,
+   position_direct_date: Option<RawDate>
+});
+ez_serde!(InsiderHolders {
+   #[serde(default)]
+   holders: Vec<InsiderHolderData>
+});
+
+ez_serde!(DefaultKeyStatistics {
+   enterprise_value: Option<RawNumber>,
+
+   #[serde(rename = "forwardPE")]
+   forward_pe: Option<RawNumber>,
+
+   peg_ratio: Option<RawNumber>,
+   price_to_book: Option<RawNumber>,
+   book_value: Option<RawNumber>,
+   beta: Option<RawNumber>,
+   shares_outstanding: Option<RawNumber>,
+   float_shares: Option<RawNumber>,
+   shares_short: Option<RawNumber>,
+   short_ratio: Option<RawNumber>,
+   short_percent_of_float: Option<RawNumber>,
+   trailing_eps: Option<RawNumber>,
+   forward_eps: Option<RawNumber>,
+   enterprise_to_revenue: Option<RawNumber>,
+   enterprise_to_ebitda: Option<RawNumber>,
+
+   // funds only
+   fund_inception_date: Option<RawDate>,
+   total_assets: Option<RawNumber>
+});
+
+ez_serde!(SummaryDetail {
+   market_cap: Option<RawNumber>,
+
+   #[serde(rename = "trailingPE")]
+   trailing_pe: Option<RawNumber>,
+
+   #[serde(rename = "forwardPE")]
+   forward_pe: Option<RawNumber>,
+
+   #[serde(rename = "priceToSalesTrailing12Months")]
+   price_to_sales: Option<RawNumber>,
+
+   beta: Option<RawNumber>,
+   dividend_rate: Option<RawNumber>,
+   dividend_yield: Option<RawNumber>,
+   ex_dividend_date: Option<RawDate>,
+   payout_ratio: Option<RawNumber>,
+   average_volume: Option<RawNumber>,
+   fifty_two_week_low: Option<RawNumber>,
+   fifty_two_week_high: Option<RawNumber>,
+   fifty_day_average: Option<RawNumber>,
+   two_hundred_day_average: Option<RawNumber>
+});
+
+ez_serde!(FeesExpenses {
+   annual_report_expense_ratio: Option<RawNumber>,
+   annual_holdings_turnover: Option<RawNumber>
+});
+
+ez_serde!(FundHoldingData {
+   symbol: Option<String>,
+   holding_name: Option<String>,
+   holding_percent: Option<RawNumber>
+});
+ez_serde!(TopHoldings {
+   #[serde(default)]
+   holdings: Vec<FundHoldingData>,
+
+   // each weighting is a single entry map - ie. `{"technology": {"raw": 0.45}}`
+   #[serde(default)]
+   sector_weightings: Vec<HashMap<String, RawNumber>>,
+
+   #[serde(default)]
+   bond_ratings: Vec<HashMap<String, RawNumber>>,
+
+   stock_position: Option<RawNumber>,
+   bond_position: Option<RawNumber>,
+   cash_position: Option<RawNumber>,
+   preferred_position: Option<RawNumber>,
+   convertible_position: Option<RawNumber>,
+   other_position: Option<RawNumber>
+});
@@ -1,15 +1,12 @@
-use reqwest::Url;
 use serde::Deserialize;
-use snafu::{ ensure, OptionExt, ResultExt };
-use std::env;
+use snafu::{ OptionExt, ResultExt };
 use std::io::{ BufRead, Cursor };
 
-use crate::{ error, Result };
+use crate::{ error, Result, YahooClient };
+use super::summary::{ DefaultKeyStatistics, FeesExpenses, TopHoldings };
 
 const DATA_VAR: &'static str = "root.App.main";
 
-const BASE_URL: &'static str = "https://finance.yahoo.com";
-
 ez_serde!(QuoteType {
    #[serde(rename = "longName")] name: String,
    #[serde(rename = "quoteType")] kind: String
@@ -36,33 +33,33 @@ ez_serde!(CompanyProfile {
 ez_serde!(FundProfile {
    #[serde(rename = "legalType")] kind: String,
 
-   family: Option<String>
+   family: Option<String>,
+
+   #[serde(rename = "categoryName")] category: Option<String>,
+   #[serde(rename = "feesExpensesInvestment")] fees: Option<FeesExpenses>
 });
 
 ez_serde!(QuoteSummaryStore {
    #[serde(rename = "fundProfile")] fund_profile: Option<FundProfile>,
    #[serde(rename = "summaryProfile")] company_profile: Option<CompanyProfile>,
-   #[serde(rename = "quoteType")] quote_type: QuoteType
+   #[serde(rename = "quoteType")] quote_type: QuoteType,
+
+   default_key_statistics: Option<DefaultKeyStatistics>,
+   top_holdings: Option<TopHoldings>
 });
 ez_serde!(Stores { #[serde(rename = "QuoteSummaryStore")] quote_summary_store: QuoteSummaryStore });
 ez_serde!(Dispatcher { stores: Stores });
 ez_serde!(Context { dispatcher: Dispatcher });
 ez_serde!(Response { context: Context });
 
-pub async fn scrape<'a>(symbol: &'a str) -> Result<Stores> {
+pub async fn scrape<'a>(client: &YahooClient, symbol: &'a str) -> Result<Stores> {
    // construct the lookup URL - encoding it so we're safe
-   let base = format!("{}/quote/{}", env::var("TEST_URL").unwrap_or(BASE_URL.to_string()), symbol);
+   let path = format!("quote/{}", symbol);
 
-   let mut url = Url::parse(base.as_str()).context(error::InternalURL { url: base })?;
+   let mut url = client.quote_page_url().join(&path).context(error::InternalURL { url: path })?;
    url.query_pairs_mut().append_pair("p", symbol);
 
-   // make the call - we do not really expect this to fail.
-   // ie - we won't 404 if the symbol doesn't exist
-   let response = reqwest::get(url.clone()).await.context(error::RequestFailed)?;
-   ensure!(
-      response.status().is_success(),
-      error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
-   );
+   let response = client.get(&url).await?;
 
    let line = Cursor::new(response.text().await.context(error::UnexpectedErrorRead { url: url.clone().to_string() })?)
       .lines()
@@ -0,0 +1,97 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{client, summary_mock};
+use tokio_test::block_on;
+use yahoo_finance::analysts::GradeAction;
+
+const MODULES: &str = "recommendationTrend,upgradeDowngradeHistory,financialData";
+
+#[test]
+fn load_trend() {
+   //! Ensure that we can load the recommendation counts by month
+
+   // GIVEN - a valid response with recommendations
+   let _m = summary_mock("analysts_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load what analysts think
+   let result = block_on(client().analysts("AAPL")).unwrap();
+
+   // THEN - we get the counts for each month
+   assert_eq!(2, result.trend.len());
+   assert_eq!("0m", result.trend[0].period);
+   assert_eq!(10, result.trend[0].strong_buy);
+   assert_eq!(22, result.trend[0].buy);
+   assert_eq!(8, result.trend[0].hold);
+   assert_eq!(2, result.trend[1].sell);
+   assert_eq!(0, result.trend[1].strong_sell);
+
+   // AND - months without a period are skipped
+   assert!(result.trend.iter().all(|trend| !trend.period.is_empty()));
+   assert!(!result.trend.iter().any(|trend| trend.strong_buy == 1 && trend.buy == 1 && trend.hold == 1));
+}
+
+#[test]
+fn load_grade_changes() {
+   //! Ensure that we can load rating changes with the firm and action
+
+   // GIVEN - a valid response with rating changes
+   let _m = summary_mock("analysts_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load what analysts think
+   let result = block_on(client().analysts("AAPL")).unwrap();
+
+   // THEN - we get each change
+   let changes = &result.grade_changes;
+   assert_eq!(3, changes.len());
+   assert_eq!(Utc.timestamp(1599840000, 0), changes[0].date);
+   assert_eq!("Wedbush", changes[0].firm);
+   assert_eq!(Some("Neutral".to_string()), changes[0].from_grade);
+   assert_eq!(Some("Outperform".to_string()), changes[0].to_grade);
+   assert_eq!(Some(GradeAction::Upgrade), changes[0].action);
+
+   // AND - initiated coverage has no previous grade
+   assert_eq!(None, changes[1].from_grade);
+   assert_eq!(Some(GradeAction::Initiated), changes[1].action);
+   assert_eq!(Some(GradeAction::Maintained), changes[2].action);
+
+   // AND - changes without a date or firm are skipped
+   assert!(changes.iter().all(|change| !change.firm.is_empty()));
+   assert!(!changes.iter().any(|change| change.firm == "No Date"));
+}
+
+#[test]
+fn load_price_target() {
+   //! Ensure that we can load the consensus price target
+
+   // GIVEN - a valid response with price targets
+   let _m = summary_mock("analysts_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load what analysts think
+   let result = block_on(client().analysts("AAPL")).unwrap();
+
+   // THEN - we get the low / mean / high and the number of analysts
+   let target = result.price_target.unwrap();
+   assert_eq!(Some(62.5), target.low);
+   assert_eq!(Some(118.22), target.mean);
+   assert_eq!(Some(120.0), target.median);
+   assert_eq!(Some(150.0), target.high);
+   assert_eq!(Some(37), target.analysts);
+   assert_eq!(Some("buy".to_string()), target.recommendation);
+}
+
+#[test]
+fn load_fund() {
+   //! Ensure that funds simply have no analyst coverage
+
+   // GIVEN - a fund without analyst coverage
+   let _m = summary_mock("analysts_data/qqq", "QQQ", MODULES).unwrap().create();
+
+   // WHEN - we load what analysts think
+   let result = block_on(client().analysts("QQQ")).unwrap();
+
+   // THEN - there is nothing
+   assert!(result.trend.is_empty());
+   assert!(result.grade_changes.is_empty());
+   assert!(result.price_target.is_none());
+}
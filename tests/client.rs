@@ -0,0 +1,199 @@
+use mockito::{mock, Matcher};
+use reqwest::header::{HeaderMap, HeaderValue};
+use std::fs::File;
+use std::io::prelude::*;
+use std::time::{Duration, Instant, SystemTime};
+use tokio_test::block_on;
+use yahoo_finance::{Interval, RetryPolicy, YahooClient};
+
+fn load_data(test_name: &str) -> std::io::Result<String> {
+   let mut file = File::open(format!("tests/history_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+   Ok(contents)
+}
+
+#[test]
+fn client_configured() {
+   //! Ensure that a configured client uses its endpoint & headers on every call
+
+   // GIVEN - a client with a custom user agent and header
+   let mut headers = HeaderMap::new();
+   headers.insert("x-test", HeaderValue::from_static("yes"));
+   let client = YahooClient::builder()
+      .chart_url(&mockito::server_url())
+      .user_agent("yahoo-finance-test")
+      .default_headers(headers)
+      .sessions(false)
+      .build()
+      .unwrap();
+
+   // AND - a server that expects them
+   let _m = mock("GET", "/AAPL?range=5d&interval=1d")
+      .match_header("user-agent", "yahoo-finance-test")
+      .match_header("x-test", "yes")
+      .with_header("content-type", "application/json")
+      .with_body(&load_data("aapl").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we load the data
+   let result = block_on(client.history_interval("AAPL", Interval::_5d)).unwrap();
+
+   // THEN - the call succeeds
+   assert_eq!(5, result.len());
+}
+
+#[test]
+#[should_panic(expected = "InvalidURL")]
+fn client_invalid_url() {
+   //! Ensure that we gracefully fail when given an endpoint that isn't a URL
+
+   // WHEN - we build a client with a bad endpoint
+   YahooClient::builder().chart_url("not a url").build().unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn client_retries() {
+   //! Ensure that we retry when Yahoo! is having problems before giving up
+
+   // GIVEN - a client that retries twice
+   let policy = RetryPolicy { max_retries: 2, base_delay: Duration::from_millis(10), max_delay: Duration::from_millis(50) };
+   let client = YahooClient::builder().chart_url(&mockito::server_url()).sessions(false).retry(policy).build().unwrap();
+
+   // AND - a server that keeps failing
+   let m = mock("GET", "/MSFT?range=5d&interval=1d").with_status(503).expect(3).create();
+
+   // WHEN - we load the data
+   let result = block_on(client.history_interval("MSFT", Interval::_5d));
+
+   // THEN - we tried 3 times before failing
+   assert!(format!("{:?}", result.err().unwrap()).contains("CallFailed"));
+   m.assert();
+}
+
+#[test]
+fn client_retry_after() {
+   //! Ensure that we wait as long as Yahoo! asks us to when throttled
+
+   // GIVEN - a client that retries once
+   let policy = RetryPolicy { max_retries: 1, base_delay: Duration::from_millis(10), max_delay: Duration::from_secs(5) };
+   let client = YahooClient::builder().chart_url(&mockito::server_url()).sessions(false).retry(policy).build().unwrap();
+
+   // AND - a server that throttles us for a second
+   let m = mock("GET", "/IBM?range=5d&interval=1d").with_status(429).with_header("retry-after", "1").expect(2).create();
+
+   // WHEN - we load the data
+   let start = Instant::now();
+   let result = block_on(client.history_interval("IBM", Interval::_5d));
+
+   // THEN - we waited for the throttle before trying again
+   assert!(result.is_err());
+   assert!(start.elapsed() >= Duration::from_secs(1));
+   m.assert();
+}
+
+#[test]
+fn client_retry_after_date() {
+   //! Ensure that we understand being throttled until a date
+
+   // GIVEN - a client that retries once
+   let policy = RetryPolicy { max_retries: 1, base_delay: Duration::from_millis(10), max_delay: Duration::from_secs(5) };
+   let client = YahooClient::builder().chart_url(&mockito::server_url()).sessions(false).retry(policy).build().unwrap();
+
+   // AND - a server that throttles us until a couple of seconds from now
+   let until = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(2));
+   let m = mock("GET", "/ORCL?range=5d&interval=1d").with_status(429).with_header("retry-after", &until).expect(2).create();
+
+   // WHEN - we load the data
+   let start = Instant::now();
+   let result = block_on(client.history_interval("ORCL", Interval::_5d));
+
+   // THEN - we waited for the throttle before trying again
+   assert!(result.is_err());
+   assert!(start.elapsed() >= Duration::from_millis(500));
+   m.assert();
+}
+
+#[test]
+fn client_retry_after_limited() {
+   //! Ensure that we never wait longer than the retry policy allows - no matter what Yahoo! asks for
+
+   // GIVEN - a client that waits at most 50ms between retries
+   let policy = RetryPolicy { max_retries: 1, base_delay: Duration::from_millis(10), max_delay: Duration::from_millis(50) };
+   let client = YahooClient::builder().chart_url(&mockito::server_url()).sessions(false).retry(policy).build().unwrap();
+
+   // AND - a server that throttles us for an hour
+   let m = mock("GET", "/INTC?range=5d&interval=1d").with_status(429).with_header("retry-after", "3600").expect(2).create();
+
+   // WHEN - we load the data
+   let start = Instant::now();
+   let result = block_on(client.history_interval("INTC", Interval::_5d));
+
+   // THEN - we only waited as long as the policy allows
+   assert!(result.is_err());
+   assert!(start.elapsed() < Duration::from_secs(5));
+   m.assert();
+}
+
+#[test]
+fn client_rate_limited() {
+   //! Ensure that calls are spaced out by the rate limit
+
+   // GIVEN - a client limited to 10 calls a second with no bursts
+   let client = YahooClient::builder().chart_url(&mockito::server_url()).sessions(false).rate_limit(10.0, 1).build().unwrap();
+   let _m = mock("GET", "/AAPL?range=5d&interval=1d")
+      .with_header("content-type", "application/json")
+      .with_body(&load_data("aapl").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we make 4 calls
+   let start = Instant::now();
+   for _ in 0..4 { block_on(client.history_interval("AAPL", Interval::_5d)).unwrap(); }
+
+   // THEN - the last 3 had to wait their turn
+   assert!(start.elapsed() >= Duration::from_millis(300));
+}
+
+#[test]
+fn client_rate_limited_session() {
+   //! Ensure that starting a session counts towards the rate limit
+
+   // GIVEN - a client limited to 10 calls a second with no bursts - along with a session
+   let client = YahooClient::builder()
+      .chart_url(&mockito::server_url())
+      .cookie_url(&format!("{}/limited_cookie", mockito::server_url()))
+      .crumb_url(&format!("{}/limited_crumb", mockito::server_url()))
+      .rate_limit(10.0, 1)
+      .build()
+      .unwrap();
+   let _cookie = mock("GET", "/limited_cookie").with_header("set-cookie", "B=abc123; Path=/").with_status(404).create();
+   let _crumb = mock("GET", "/limited_crumb").with_body("Xy1.ab/Cd3").with_status(200).create();
+   let _m = mock("GET", "/AAPL")
+      .match_query(Matcher::UrlEncoded("crumb".to_string(), "Xy1.ab/Cd3".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(&load_data("aapl").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we make a call - along with the cookie & crumb calls
+   let start = Instant::now();
+   block_on(client.history_interval("AAPL", Interval::_5d)).unwrap();
+
+   // THEN - the last 2 had to wait their turn
+   assert!(start.elapsed() >= Duration::from_millis(200));
+}
+
+#[test]
+#[should_panic(expected = "InvalidRateLimit")]
+fn client_invalid_rate_limit() {
+   //! Ensure that we gracefully fail when the rate limit would never allow a call
+
+   // WHEN - we build a client that can't make any calls
+   YahooClient::builder().rate_limit(0.0, 1).build().unwrap();
+
+   // THEN - we get an error
+}
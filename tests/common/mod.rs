@@ -0,0 +1,49 @@
+//! Fixtures shared by the tests of everything loaded from Yahoo! over HTTP.
+#![allow(dead_code)]
+
+use mockito::{mock, Matcher, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use yahoo_finance::YahooClient;
+
+/// A client that calls the mock server rather than the live one
+pub fn client() -> YahooClient {
+   YahooClient::builder()
+      .chart_url(&mockito::server_url())
+      .options_url(&format!("{}/v7/finance/options", mockito::server_url()))
+      .quote_url(&format!("{}/v7/finance/quote", mockito::server_url()))
+      .quote_page_url(&mockito::server_url())
+      .quote_summary_url(&mockito::server_url())
+      .sessions(false)
+      .build()
+      .unwrap()
+}
+
+fn load_data(file_name: &str) -> std::io::Result<String> {
+   let mut file = File::open(format!("tests/{}", file_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+   Ok(contents)
+}
+
+/// Serves up a test data file on a path - ie. `data_mock("/AAPL?range=5d&interval=1d", "history_data/aapl.json")`
+pub fn data_mock(path: &str, file_name: &str) -> std::io::Result<Mock> {
+   let content_type = if file_name.ends_with(".html") { "text/html" } else { "application/json" };
+
+   Ok(mock("GET", path)
+      .with_header("content-type", content_type)
+      .with_body(&load_data(file_name)?)
+      .with_status(200))
+}
+
+/// Serves up a quote page - ie. `page_mock("statistics_data/aapl", "AAPL")` for `tests/statistics_data/aapl.html`
+pub fn page_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   data_mock(&format!("/quote/{symbol}?p={symbol}", symbol=symbol), &format!("{}.html", test_name))
+}
+
+/// Serves up the quote summary modules for a symbol - ie. `summary_mock("earnings_data/aapl", "AAPL", "earningsHistory")`
+/// for `tests/earnings_data/aapl.json`
+pub fn summary_mock(test_name: &str, symbol: &str, modules: &str) -> std::io::Result<Mock> {
+   Ok(data_mock(&format!("/{}", symbol), &format!("{}.json", test_name))?
+      .match_query(Matcher::UrlEncoded("modules".to_string(), modules.to_string())))
+}
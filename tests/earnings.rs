@@ -0,0 +1,91 @@
+mod common;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use common::{client, summary_mock};
+use tokio_test::block_on;
+
+const MODULES: &str = "earningsHistory,calendarEvents,earningsTrend";
+
+#[test]
+fn load_history() {
+   //! Ensure that we can load past earnings results with their surprise
+
+   // GIVEN - a valid response with earnings history
+   let _m = summary_mock("earnings_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the earnings
+   let result = block_on(client().earnings("AAPL")).unwrap();
+
+   // THEN - we get every quarter with its actual & estimated EPS
+   assert_eq!(2, result.history.len());
+   assert_eq!(Some(Utc.timestamp(1569801600, 0)), result.history[0].quarter);
+   assert_eq!(Some("-4q".to_string()), result.history[0].period);
+   assert_eq!(Some(0.76), result.history[0].eps_actual);
+   assert_eq!(Some(0.71), result.history[0].eps_estimate);
+   assert_eq!(Some(0.143), result.history[1].surprise_percent);
+}
+
+#[test]
+fn load_calendar() {
+   //! Ensure that we can load the range the next announcement falls in
+
+   // GIVEN - a valid response with an unconfirmed earnings date
+   let _m = summary_mock("earnings_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the earnings
+   let result = block_on(client().earnings("AAPL")).unwrap();
+
+   // THEN - we get the start & end of the range along with the estimates
+   let calendar = result.calendar.unwrap();
+   assert_eq!(vec![Utc.timestamp(1603929600, 0), Utc.timestamp(1604361600, 0)], calendar.dates);
+   assert_eq!(Some(0.7), calendar.eps_average);
+   assert_eq!(Some(69306000000.0), calendar.revenue_high);
+}
+
+#[test]
+fn load_estimates() {
+   //! Ensure that we can load the analyst estimates for upcoming periods
+
+   // GIVEN - a valid response with earnings estimates
+   let _m = summary_mock("earnings_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the earnings
+   let result = block_on(client().earnings("AAPL")).unwrap();
+
+   // THEN - we get the EPS and revenue consensus for each period
+   let estimate = &result.estimates[0];
+   assert_eq!("0q", estimate.period);
+   assert_eq!(Some(NaiveDate::from_ymd(2020, 9, 30)), estimate.end_date);
+
+   let eps = estimate.eps.as_ref().unwrap();
+   assert_eq!(Some(0.7), eps.average);
+   assert_eq!(Some(0.76), eps.year_ago);
+   assert_eq!(Some(27), eps.analysts);
+
+   let revenue = estimate.revenue.as_ref().unwrap();
+   assert_eq!(Some(64040000000.0), revenue.year_ago);
+   assert_eq!(Some(25), revenue.analysts);
+
+   // AND - periods without estimates are left empty
+   assert_eq!(None, result.estimates[1].end_date);
+   assert_eq!(None, result.estimates[1].eps.as_ref().unwrap().average);
+
+   // AND - estimates without a period are skipped
+   assert_eq!(2, result.estimates.len());
+}
+
+#[test]
+fn load_fund() {
+   //! Ensure that funds simply have no earnings
+
+   // GIVEN - a fund without earnings
+   let _m = summary_mock("earnings_data/qqq", "QQQ", MODULES).unwrap().create();
+
+   // WHEN - we load the earnings
+   let result = block_on(client().earnings("QQQ")).unwrap();
+
+   // THEN - there is nothing
+   assert!(result.history.is_empty());
+   assert!(result.calendar.is_none());
+   assert!(result.estimates.is_empty());
+}
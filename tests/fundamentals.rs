@@ -0,0 +1,82 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{client, summary_mock};
+use tokio_test::block_on;
+
+const MODULES: &str = "incomeStatementHistory,incomeStatementHistoryQuarterly,balanceSheetHistory,balanceSheetHistoryQuarterly,cashflowStatementHistory,cashflowStatementHistoryQuarterly";
+
+#[test]
+fn load_statements() {
+   //! Ensure that we can load the annual and quarterly statements for a company
+
+   // GIVEN - a valid response with financial statements
+   let _m = summary_mock("fundamentals_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the fundamentals
+   let result = block_on(client().fundamentals("AAPL")).unwrap();
+
+   // THEN - we get every income statement, most recent first
+   let income = &result.annual.income_statements;
+   assert_eq!(2, income.len());
+   assert_eq!(Some(Utc.timestamp(1569628800, 0)), income[0].end_date);
+   assert_eq!(Some(260174000000.0), income[0].total_revenue);
+   assert_eq!(Some(-3576000000.0), income[0].interest_expense);
+   assert_eq!(Some(59531000000.0), income[1].net_income);
+
+   // AND - the balance sheets and cash flows
+   assert_eq!(Some(338516000000.0), result.annual.balance_sheets[0].total_assets);
+   assert_eq!(Some(248028000000.0), result.annual.balance_sheets[0].total_liabilities);
+   assert_eq!(Some(69391000000.0), result.annual.cash_flows[0].operating_cash_flow);
+   assert_eq!(Some(-10495000000.0), result.annual.cash_flows[0].capital_expenditures);
+
+   // AND - the quarterly statements
+   assert_eq!(Some(59685000000.0), result.quarterly.income_statements[0].total_revenue);
+   assert!(result.quarterly.balance_sheets.is_empty());
+   assert!(result.quarterly.cash_flows.is_empty());
+}
+
+#[test]
+fn load_missing_values() {
+   //! Ensure that values Yahoo! doesn't have are left empty
+
+   // GIVEN - a response where some values are empty
+   let _m = summary_mock("fundamentals_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the fundamentals
+   let result = block_on(client().fundamentals("AAPL")).unwrap();
+
+   // THEN - the empty and missing values are None
+   assert_eq!(None, result.annual.balance_sheets[0].goodwill);
+   assert_eq!(None, result.annual.balance_sheets[0].net_receivables);
+   assert_eq!(None, result.quarterly.income_statements[0].research_development);
+}
+
+#[test]
+fn load_fund() {
+   //! Ensure that funds simply have no statements
+
+   // GIVEN - a fund without financial statements
+   let _m = summary_mock("fundamentals_data/qqq", "QQQ", MODULES).unwrap().create();
+
+   // WHEN - we load the fundamentals
+   let result = block_on(client().fundamentals("QQQ")).unwrap();
+
+   // THEN - there are no statements
+   assert!(result.annual.income_statements.is_empty());
+   assert!(result.annual.balance_sheets.is_empty());
+   assert!(result.quarterly.cash_flows.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "SummaryFailed")]
+fn load_unknown() {
+   //! Ensure that Yahoo! failing to find the symbol is an error
+
+   // GIVEN - a response for a symbol Yahoo! doesn't know
+   let _m = summary_mock("fundamentals_data/unknown", "NOPE", MODULES).unwrap().create();
+
+   // WHEN - we load the fundamentals
+   // THEN - we get an error
+   block_on(client().fundamentals("NOPE")).unwrap();
+}
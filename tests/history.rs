@@ -1,29 +1,26 @@
-use chrono::{Duration, Utc};
-use mockito::{mock, Mock};
-use std::env;
-use std::fs::File;
-use std::io::prelude::*;
+mod common;
+
+use chrono::{Duration, NaiveDate, Utc};
+use common::{client, data_mock};
+use mockito::{Matcher, Mock};
 use tokio_test::block_on;
-use yahoo_finance::{history, Interval};
+use yahoo_finance::{history, history::Granularity, Interval};
 
 fn base_mock(test_name: &str, symbol: &str, query: &str) -> std::io::Result<Mock> {
-   // Tell the actual code to use a test URL rather than the live one
-   env::set_var("TEST_URL", mockito::server_url());
-
-   // Load the simulated Yahoo data we want to test against
-   let mut file = File::open(format!("tests/history_data/{}.json", test_name))?;
-   let mut contents = String::new();
-   file.read_to_string(&mut contents)?;
+   data_mock(&format!("/{symbol}?{query}", symbol=symbol, query=query), &format!("history_data/{}.json", test_name))
+}
 
-   // Serve up the test data on the test URL
-   Ok(mock("GET", format!("/{symbol}?{query}", symbol=symbol, query=query).as_str())
-      .with_header("content-type", "application/json")
-      .with_body(&contents)
-      .with_status(200))
+/// Serves up the test data for any date range matching the query
+fn range_mock(test_name: &str, symbol: &str, query: Matcher) -> std::io::Result<Mock> {
+   Ok(data_mock(&format!("/{}", symbol), &format!("history_data/{}.json", test_name))?.match_query(query))
 }
 
+fn build_granularity(granularity: Granularity) -> Matcher { Matcher::UrlEncoded("interval".to_string(), granularity.to_string()) }
+
 fn build_interval(interval: Interval) -> String { format!("range={r}&interval={i}", r=interval, i=Interval::_1d) }
 
+fn build_intraday(interval: Interval, granularity: Granularity) -> String { format!("range={r}&interval={i}", r=interval, i=granularity) }
+
 #[test]
 fn retrieve_valid() {
    //! Ensure that we can load for valid companies
@@ -33,7 +30,7 @@ fn retrieve_valid() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we load the data
-   let result = block_on(history::retrieve(symbol)).unwrap();
+   let result = block_on(client().history(symbol)).unwrap();
    assert!(result.len() > 0)
 }
 
@@ -47,7 +44,7 @@ fn retrieve_invalid_symbol() {
    let _m = base_mock("not_found", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we load the data
-   block_on(history::retrieve(symbol)).unwrap();
+   block_on(client().history(symbol)).unwrap();
 
    // THEN - we get an error
 }
@@ -62,11 +59,27 @@ fn retrieve_interval_invalid() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get a date range where the start date is after the end date
-   block_on(history::retrieve_interval(symbol, Interval::_1m)).unwrap();
+   block_on(client().history_interval(symbol, Interval::_1m)).unwrap();
 
    // THEN - we get an error
 }
 
+#[test]
+fn retrieve_interval_shared() {
+   //! Ensure that the free functions work through the shared client - from one runtime after another
+
+   // GIVEN - an intraday interval, which fails before any call goes out
+   let symbol = "AAPL";
+
+   // WHEN - we use the free function from two runtimes in a row
+   let first = block_on(history::retrieve_interval(symbol, Interval::_1m));
+   let second = block_on(history::retrieve_interval(symbol, Interval::_1m));
+
+   // THEN - both fail the same way
+   assert!(format!("{:?}", first.unwrap_err()).contains("NoIntraday"));
+   assert!(format!("{:?}", second.unwrap_err()).contains("NoIntraday"));
+}
+
 #[test]
 #[should_panic(expected = "InvalidStartDate")]
 fn retrieve_range_invalid1() {
@@ -77,7 +90,7 @@ fn retrieve_range_invalid1() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get a date range where the start date is after the end date
-   block_on(history::retrieve_range(symbol, Utc::now() - Duration::days(10), Some(Utc::now() - Duration::days(15)))).unwrap();
+   block_on(client().history_range(symbol, Utc::now() - Duration::days(10), Some(Utc::now() - Duration::days(15)))).unwrap();
 
    // THEN - we get an error
 }
@@ -92,7 +105,7 @@ fn retrieve_range_invalid2() {
    let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get a date range where the start date is after the end date
-   block_on(history::retrieve_range(symbol, Utc::now() + Duration::days(10), None)).unwrap();
+   block_on(client().history_range(symbol, Utc::now() + Duration::days(10), None)).unwrap();
 
    // THEN - we get an error
 }
@@ -107,7 +120,7 @@ fn retrieve_no_quote_data() {
    let _m = base_mock("no_quote_data", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get data where the there is basically no data
-   let result = block_on(history::retrieve(symbol)).unwrap();
+   let result = block_on(client().history(symbol)).unwrap();
    assert!(result.len() == 0)
 }
 
@@ -122,7 +135,230 @@ fn retrieve_no_timestamp_data() {
    let _m = base_mock("no_timestamp_data", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
 
    // WHEN - we get data where the there are no quotes
-   block_on(history::retrieve(symbol)).unwrap();
+   block_on(client().history(symbol)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_intraday_valid() {
+   //! Ensure that we can load intraday bars and skip the incomplete trailing bar
+
+   // GIVEN - a valid response with 5 minute bars
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_intraday", symbol, build_intraday(Interval::_1d, Granularity::_5m).as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(client().history_intraday(symbol, Granularity::_5m, Interval::_1d)).unwrap();
+
+   // THEN - we get the complete bars with millisecond timestamps
+   assert_eq!(4, result.len());
+   assert_eq!(1588339800000, result[0].timestamp);
+   assert_eq!(1588340700000, result[3].timestamp);
+}
+
+#[test]
+#[should_panic(expected = "LookbackExceeded")]
+fn retrieve_intraday_lookback() {
+   //! Ensure that we gracefully fail when asking for more intraday data than Yahoo! keeps
+
+   // GIVEN - a valid response for an valid symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_intraday", symbol, build_intraday(Interval::_1mo, Granularity::_1m).as_str()).unwrap().create();
+
+   // WHEN - we ask for a month of 1 minute bars
+   block_on(client().history_intraday(symbol, Granularity::_1m, Interval::_1mo)).unwrap();
 
    // THEN - we get an error
 }
+
+#[test]
+fn retrieve_range_weekly() {
+   //! Ensure that we can load bars of a different size for a date range
+
+   // GIVEN - a valid response for weekly bars
+   let symbol = "AAPL";
+   let _m = range_mock("aapl", symbol, build_granularity(Granularity::_1wk)).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(client().history_range_granularity(symbol, Utc::now() - Duration::days(365), None, Granularity::_1wk)).unwrap();
+
+   // THEN - we get the bars back
+   assert_eq!(5, result.len());
+}
+
+#[test]
+#[should_panic(expected = "LookbackExceeded")]
+fn retrieve_range_intraday_lookback() {
+   //! Ensure that we gracefully fail when the start date is older than Yahoo! keeps intraday data
+
+   // GIVEN - a valid response for an valid symbol
+   let symbol = "AAPL";
+   let _m = range_mock("aapl_intraday", symbol, build_granularity(Granularity::_5m)).unwrap().create();
+
+   // WHEN - we ask for 5 minute bars starting 90 days ago
+   block_on(client().history_range_granularity(symbol, Utc::now() - Duration::days(90), None, Granularity::_5m)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_adjusted_valid() {
+   //! Ensure that we can load the adjusted close alongside the raw bars
+
+   // GIVEN - a valid response where the first day is before a dividend
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_adjusted", symbol, build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(client().history_adjusted(symbol, Interval::_5d)).unwrap();
+
+   // THEN - we get both the raw & adjusted close
+   assert_eq!(2, result.len());
+   assert!((result[0].close - 310.13).abs() < 0.001);
+   assert!((result[0].adjusted_close - 309.36).abs() < 0.001);
+
+   // AND - back-adjusting scales the whole bar
+   let adjusted = result[0].back_adjusted();
+   assert!((adjusted.close - 309.36).abs() < 0.001);
+   assert!(adjusted.open < result[0].open);
+   assert!((result[1].back_adjusted().open - result[1].open).abs() < 0.001);
+}
+
+#[test]
+#[should_panic(expected = "no adjusted close data")]
+fn retrieve_adjusted_missing() {
+   //! Ensure that we gracefully fail when Yahoo! doesn't send the adjusted close
+
+   // GIVEN - a valid response with no adjusted close data
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_intraday", symbol, build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(client().history_adjusted(symbol, Interval::_5d)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_events_valid() {
+   //! Ensure that we can load dividends & splits in date order
+
+   // GIVEN - a valid response with 2 dividends and a split
+   let symbol = "AAPL";
+   let query = Matcher::UrlEncoded("events".to_string(), "div,split".to_string());
+   let _m = range_mock("aapl_events", symbol, query).unwrap().create();
+
+   // WHEN - we load the events
+   let result = block_on(client().history_events(symbol, Utc::now() - Duration::days(365), None)).unwrap();
+
+   // THEN - we get typed events in date order
+   assert_eq!(2, result.dividends.len());
+   assert_eq!(1588944600, result.dividends[0].ex_date.timestamp());
+   assert!((result.dividends[1].amount - 0.82).abs() < 0.001);
+
+   assert_eq!(1, result.splits.len());
+   assert_eq!(1598880600, result.splits[0].date.timestamp());
+   assert!((result.splits[0].numerator - 4.0).abs() < 0.001);
+   assert!((result.splits[0].denominator - 1.0).abs() < 0.001);
+}
+
+#[test]
+fn retrieve_events_none() {
+   //! Ensure that a symbol without any corporate actions has no events
+
+   // GIVEN - a valid response without an events block
+   let symbol = "AAPL";
+   let query = Matcher::UrlEncoded("events".to_string(), "div,split".to_string());
+   let _m = range_mock("aapl", symbol, query).unwrap().create();
+
+   // WHEN - we load the events
+   let result = block_on(client().history_events(symbol, Utc::now() - Duration::days(365), None)).unwrap();
+
+   // THEN - there is nothing to report
+   assert!(result.dividends.is_empty());
+   assert!(result.splits.is_empty());
+}
+
+#[test]
+fn retrieve_chart_valid() {
+   //! Ensure that we get the chart metadata along with the bars
+
+   // GIVEN - a valid response and stock symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load the chart
+   let result = block_on(client().history_chart(symbol, Interval::_5d)).unwrap();
+
+   // THEN - we know what we loaded
+   assert_eq!(5, result.bars.len());
+   assert_eq!("AAPL", result.meta.symbol);
+   assert_eq!(Some("USD".to_string()), result.meta.currency);
+   assert_eq!(Some("NMS".to_string()), result.meta.exchange_name);
+   assert_eq!(Some("EQUITY".to_string()), result.meta.instrument_type);
+   assert_eq!(Some(345479400), result.meta.first_trade_date.map(|date| date.timestamp()));
+   assert_eq!(Some(-14400), result.meta.gmt_offset);
+   assert_eq!(Some("America/New_York".to_string()), result.meta.exchange_timezone_name);
+   assert_eq!(Some(2), result.meta.price_hint);
+   assert_eq!(1588339800, result.meta.regular_market.unwrap().start.timestamp());
+   assert!(result.meta.valid_ranges.contains(&"ytd".to_string()));
+}
+
+#[test]
+fn retrieve_chart_local() {
+   //! Ensure that bars are dated by the exchange rather than by UTC
+
+   // GIVEN - a valid response for a Sydney listed symbol
+   let symbol = "CBA.AX";
+   let _m = base_mock("cba", symbol, build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load the chart in exchange local time
+   let result = block_on(client().history_chart(symbol, Interval::_5d)).unwrap().local_bars().unwrap();
+
+   // THEN - the bars land on the Sydney trading date even though it is the day before in UTC
+   assert_eq!(2, result.len());
+   assert_eq!(36000, result[0].datetime.offset().local_minus_utc());
+   assert_eq!(NaiveDate::from_ymd(2020, 5, 1), result[0].trading_date());
+   assert_eq!(NaiveDate::from_ymd(2020, 5, 4), result[1].trading_date());
+}
+
+#[test]
+fn retrieve_many_valid() {
+   //! Ensure that one bad symbol doesn't fail the rest of a batch
+
+   // GIVEN - valid responses for a good and a bad symbol
+   let _good = base_mock("aapl", "AAPL", build_interval(Interval::_5d).as_str()).unwrap().create();
+   let _bad = base_mock("not_found", "FUBAR", build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load both at once
+   let result = block_on(client().history_many(&["AAPL", "FUBAR"], Interval::_5d)).unwrap();
+
+   // THEN - each symbol gets its own result
+   assert_eq!(2, result.len());
+   assert_eq!(5, result["AAPL"].as_ref().unwrap().len());
+   assert!(result["FUBAR"].is_err());
+}
+
+#[test]
+fn retrieve_chart_sparse_meta() {
+   //! Ensure that metadata Yahoo! leaves out doesn't stop the chart from loading
+
+   // GIVEN - a response without the exchange, timezone or first trade date in its metadata
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_sparse_meta", symbol, build_interval(Interval::_5d).as_str()).unwrap().create();
+
+   // WHEN - we load the chart
+   let result = block_on(client().history_chart(symbol, Interval::_5d)).unwrap();
+
+   // THEN - the bars load with the missing metadata left empty
+   assert_eq!(5, result.bars.len());
+   assert_eq!(None, result.meta.exchange_name);
+   assert_eq!(None, result.meta.instrument_type);
+   assert_eq!(None, result.meta.first_trade_date);
+   assert_eq!(None, result.meta.gmt_offset);
+   assert_eq!(None, result.meta.timezone());
+
+   // AND - without a timezone or offset the bars can't be put in local time
+   assert!(result.local_bars().is_none());
+}
@@ -0,0 +1,101 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{client, summary_mock};
+use tokio_test::block_on;
+
+const MODULES: &str = "majorHoldersBreakdown,institutionOwnership,fundOwnership,insiderTransactions,insiderHolders";
+
+#[test]
+fn load_major_holders() {
+   //! Ensure that we can load the ownership breakdown
+
+   // GIVEN - a valid response with the ownership breakdown
+   let _m = summary_mock("holders_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the holders
+   let result = block_on(client().holders("AAPL")).unwrap();
+
+   // THEN - we get the breakdown
+   let major = result.major_holders.unwrap();
+   assert_eq!(Some(0.00066), major.insiders_percent_held);
+   assert_eq!(Some(0.62135), major.institutions_percent_held);
+   assert_eq!(Some(4296), major.institutions_count);
+}
+
+#[test]
+fn load_institutions_and_funds() {
+   //! Ensure that we can load the top institutional and mutual fund holders
+
+   // GIVEN - a valid response with institutional and fund holders
+   let _m = summary_mock("holders_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the holders
+   let result = block_on(client().holders("AAPL")).unwrap();
+
+   // THEN - we get each holder's position
+   assert_eq!(2, result.institutions.len());
+   let vanguard = &result.institutions[0];
+   assert_eq!("Vanguard Group, Inc. (The)", vanguard.holder);
+   assert_eq!(Some(1311852764), vanguard.shares);
+   assert_eq!(Some(147298229344.0), vanguard.value);
+   assert_eq!(Some(0.0766), vanguard.percent_held);
+   assert_eq!(Some(Utc.timestamp(1593475200, 0)), vanguard.report_date);
+
+   assert_eq!(1, result.funds.len());
+   assert_eq!("Vanguard Total Stock Market Index Fund", result.funds[0].holder);
+
+   // AND - holders without a name are skipped
+   assert!(result.institutions.iter().all(|holder| !holder.holder.is_empty()));
+}
+
+#[test]
+fn load_insiders() {
+   //! Ensure that we can load insider transactions and the insider roster
+
+   // GIVEN - a valid response with insider activity
+   let _m = summary_mock("holders_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the holders
+   let result = block_on(client().holders("AAPL")).unwrap();
+
+   // THEN - we get each transaction
+   let sale = &result.insider_transactions[0];
+   assert_eq!("COOK TIMOTHY D", sale.insider);
+   assert_eq!(Some("Chief Executive Officer".to_string()), sale.relation);
+   assert_eq!(Some("Sale at price 119.37 per share.".to_string()), sale.description);
+   assert_eq!(Some(265160), sale.shares);
+   assert_eq!(Some(31651234.0), sale.value);
+   assert_eq!(Some(true), sale.direct);
+
+   // AND - transactions without a description or value are left empty
+   let grant = &result.insider_transactions[1];
+   assert_eq!(None, grant.description);
+   assert_eq!(None, grant.value);
+   assert_eq!(Some(false), grant.direct);
+
+   // AND - we get the roster
+   assert_eq!(1, result.insiders.len());
+   assert_eq!(Some("Sale".to_string()), result.insiders[0].latest_transaction);
+   assert_eq!(Some(837374), result.insiders[0].position_direct);
+
+   // AND - transactions and insiders without a name are skipped
+   assert!(!result.insider_transactions.iter().any(|transaction| transaction.shares == Some(100)));
+   assert!(result.insiders.iter().all(|insider| !insider.name.is_empty()));
+}
+
+#[test]
+fn load_fund() {
+   //! Ensure that symbols without holder data simply have none
+
+   // GIVEN - a fund without holder data
+   let _m = summary_mock("holders_data/qqq", "QQQ", MODULES).unwrap().create();
+
+   // WHEN - we load the holders
+   let result = block_on(client().holders("QQQ")).unwrap();
+
+   // THEN - there is nothing
+   assert!(result.major_holders.is_none());
+   assert!(result.institutions.is_empty());
+   assert!(result.insiders.is_empty());
+}
@@ -0,0 +1,325 @@
+#![cfg(feature = "test-util")]
+
+use futures::StreamExt;
+use std::time::Duration;
+use tokio::time::{delay_for, timeout};
+use tokio_test::block_on;
+use yahoo_finance::test_util::{quote, MockStreamer};
+use yahoo_finance::{ConnectionState, ReconnectPolicy, StreamEvent, StreamQuote, YahooClient};
+
+fn client(server: &MockStreamer) -> YahooClient {
+   YahooClient::builder().streamer_url(&server.url()).build().unwrap()
+}
+
+/// Waits a little while for a condition to hold - the server sees what the client sends in the background
+async fn eventually(check: impl Fn() -> bool) -> bool {
+   for _ in 0..100 {
+      if check() { return true; }
+      delay_for(Duration::from_millis(10)).await;
+   }
+   check()
+}
+
+#[test]
+fn quotes_streamed() {
+   //! Ensure that quotes sent by the server come out of the stream
+
+   block_on(async {
+      // GIVEN - a streamer connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let mut stream = streamer.stream_quotes().await.unwrap();
+
+      // WHEN - the server sends a quote
+      server.quote(&StreamQuote { day_volume: 1000, bid: 112.25, ..quote("AAPL", 112.5) });
+
+      // THEN - we get the quote
+      let received = timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap().unwrap();
+      assert_eq!("AAPL", received.symbol);
+      assert_eq!(112.5, received.price);
+      assert_eq!(112.25, received.bid);
+      assert_eq!(1000, received.day_volume);
+
+      // AND - the server got our subscription
+      assert!(eventually(|| server.received() == vec![r#"{"subscribe":["AAPL"]}"#.to_string()]).await);
+   });
+}
+
+#[test]
+fn bad_frame_skippable() {
+   //! Ensure that a frame that can't be decoded is an error that doesn't end the stream
+
+   block_on(async {
+      // GIVEN - a streamer connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let mut stream = streamer.stream().await.unwrap();
+
+      // WHEN - the server sends garbage followed by a quote
+      server.text("not a quote");
+      server.quote(&quote("AAPL", 112.5));
+
+      // THEN - the garbage is an error
+      let err = timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap().unwrap_err();
+      assert!(format!("{:?}", err).contains("DecodeFailed"));
+
+      // AND - the quote still comes through
+      let received = timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap().unwrap();
+      assert_eq!(112.5, received.price);
+   });
+}
+
+#[test]
+fn resilient_bad_frame_skippable() {
+   //! Ensure that a resilient stream reports frames it can't decode and carries on
+
+   block_on(async {
+      // GIVEN - a resilient stream connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let events = streamer.stream_resilient(ReconnectPolicy::default()).await;
+
+      // WHEN - the server sends garbage followed by a quote
+      server.text("not a quote");
+      server.quote(&quote("AAPL", 112.5));
+
+      // THEN - the garbage is an error, then the quote comes through on the same connection
+      let events = events
+         .filter(|event| futures::future::ready(!matches!(event, StreamEvent::State(_))))
+         .take(2)
+         .collect::<Vec<_>>();
+      let events = timeout(Duration::from_secs(5), events).await.expect("the stream did not carry on");
+      assert!(matches!(&events[0], StreamEvent::Error(err) if format!("{:?}", err).contains("DecodeFailed")));
+      assert!(matches!(&events[1], StreamEvent::Quote(received) if received.price == 112.5));
+      assert_eq!(1, server.connections());
+
+      streamer.stop().await;
+   });
+}
+
+#[test]
+fn ping_answered() {
+   //! Ensure that the streamer answers pings from the server
+
+   block_on(async {
+      // GIVEN - a streamer connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let mut stream = streamer.stream().await.unwrap();
+
+      // WHEN - the server pings us (with a quote to know the ping was read)
+      server.ping();
+      server.quote(&quote("AAPL", 112.5));
+      timeout(Duration::from_secs(5), stream.next()).await.unwrap().unwrap().unwrap();
+
+      // THEN - the server gets a pong back
+      assert!(eventually(|| server.pongs() > 0).await);
+   });
+}
+
+#[test]
+fn close_ends_stream() {
+   //! Ensure that the stream ends when the server closes the connection
+
+   block_on(async {
+      // GIVEN - a streamer connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let mut stream = streamer.stream().await.unwrap();
+
+      // WHEN - the server closes the connection
+      server.close();
+
+      // THEN - the stream ends
+      let next = timeout(Duration::from_secs(5), stream.next()).await.expect("the stream did not end");
+      assert!(next.is_none());
+   });
+}
+
+#[test]
+fn subscribe_while_streaming() {
+   //! Ensure that subscribing while streaming goes out over the live connection
+
+   block_on(async {
+      // GIVEN - a streamer connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let _stream = streamer.stream().await.unwrap();
+
+      // WHEN - we subscribe to another symbol
+      streamer.subscribe(vec!["MSFT"]);
+
+      // THEN - the server gets the original subscription followed by the new one
+      let expected = vec![r#"{"subscribe":["AAPL"]}"#.to_string(), r#"{"subscribe":["MSFT"]}"#.to_string()];
+      assert!(eventually(|| server.received() == expected).await);
+   });
+}
+
+#[test]
+fn resilient_reconnects() {
+   //! Ensure that a resilient stream reconnects and resubscribes when the connection drops
+
+   block_on(async {
+      // GIVEN - a resilient stream connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let policy = ReconnectPolicy { base_delay: Duration::from_millis(10), max_delay: Duration::from_millis(10), ..ReconnectPolicy::default() };
+      let events = streamer.stream_resilient(policy).await;
+
+      // WHEN - the connection drops between quotes
+      server.quote(&quote("AAPL", 112.5));
+      server.disconnect();
+      server.quote(&quote("AAPL", 113.0));
+
+      // THEN - both quotes come through, with a reconnect in between
+      let events = events
+         .take_while(|event| futures::future::ready(!matches!(event, StreamEvent::Quote(received) if received.price == 113.0)))
+         .map(|event| match event {
+            StreamEvent::State(ConnectionState::Connecting) => "connecting",
+            StreamEvent::State(ConnectionState::Connected) => "connected",
+            StreamEvent::State(ConnectionState::Disconnected { .. }) => "disconnected",
+            StreamEvent::State(ConnectionState::Reconnecting { attempt: 1, .. }) => "reconnecting",
+            StreamEvent::Quote(_) => "quote",
+            _ => "unexpected"
+         })
+         .collect::<Vec<_>>();
+      let events = timeout(Duration::from_secs(5), events).await.expect("the stream did not reconnect");
+      assert_eq!(vec!["connecting", "connected", "quote", "disconnected", "reconnecting", "connecting", "connected"], events);
+
+      // AND - the streamer subscribed on both connections
+      assert_eq!(2, server.connections());
+      assert!(eventually(|| server.received().len() == 2).await);
+
+      streamer.stop().await;
+   });
+}
+
+#[test]
+fn resilient_gives_up() {
+   //! Ensure that a server that accepts and then drops every connection backs off and gives up
+
+   block_on(async {
+      // GIVEN - a server that drops each connection straight away
+      let server = MockStreamer::start().await.unwrap();
+      for _ in 0..3 { server.disconnect(); }
+
+      // WHEN - we stream resiliently with a couple of reconnects
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let base_delay = Duration::from_millis(20);
+      let policy = ReconnectPolicy { base_delay, max_delay: Duration::from_secs(1), max_attempts: Some(2), ..ReconnectPolicy::default() };
+      let events = streamer.stream_resilient(policy).await
+         .filter_map(|event| futures::future::ready(match event {
+            StreamEvent::State(ConnectionState::Reconnecting { attempt, delay }) => Some((attempt, delay)),
+            _ => None
+         }))
+         .collect::<Vec<_>>();
+
+      // THEN - the stream ends after the last attempt
+      let reconnects = timeout(Duration::from_secs(5), events).await.expect("the stream did not give up");
+      assert_eq!(vec![1, 2], reconnects.iter().map(|(attempt, _)| *attempt).collect::<Vec<_>>());
+
+      // AND - the delay grew between the attempts
+      assert!(reconnects[0].1 <= base_delay);
+      assert!(reconnects[1].1 >= base_delay);
+
+      // AND - every attempt connected
+      assert_eq!(3, server.connections());
+   });
+}
+
+#[test]
+fn resilient_dropped_closes() {
+   //! Ensure that dropping a resilient stream closes its connection right away
+
+   block_on(async {
+      // GIVEN - a resilient stream connected to the mock server
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let events = streamer.stream_resilient(ReconnectPolicy::default()).await;
+      assert!(eventually(|| server.connections() == 1).await);
+
+      // WHEN - we drop the stream
+      let dropped = std::time::Instant::now();
+      drop(events);
+
+      // THEN - the server sees the connection closed, long before it would go stale
+      assert!(eventually(|| server.closes() == 1).await);
+      assert!(dropped.elapsed() < Duration::from_secs(2));
+
+      // AND - no reconnect follows
+      delay_for(Duration::from_millis(100)).await;
+      assert_eq!(1, server.connections());
+   });
+}
+
+#[test]
+fn resilient_stale_while_sending() {
+   //! Ensure that a connection goes stale without incoming messages, even while we keep sending
+
+   block_on(async {
+      // GIVEN - a resilient stream to a server that never sends anything
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let policy = ReconnectPolicy { stale_after: Duration::from_millis(300), max_attempts: Some(0), ..ReconnectPolicy::default() };
+      let mut events = streamer.stream_resilient(policy).await;
+
+      // WHEN - we keep changing the subscriptions
+      let started = std::time::Instant::now();
+      let disconnected = async {
+         while let Some(event) = events.next().await {
+            if let StreamEvent::State(ConnectionState::Disconnected { reason }) = event { return reason; }
+         }
+         panic!("the stream ended without disconnecting");
+      };
+      let subscribing = async {
+         for i in 0..100 {
+            streamer.subscribe(vec![format!("SYM{}", i).as_str()]);
+            delay_for(Duration::from_millis(50)).await;
+         }
+      };
+      let reason = tokio::select! {
+         reason = disconnected => reason,
+         _ = subscribing => panic!("the connection never went stale")
+      };
+
+      // THEN - the connection still went stale on time
+      assert!(reason.starts_with("no messages"));
+      assert!(started.elapsed() < Duration::from_secs(2));
+   });
+}
+
+#[test]
+fn stream_replaced() {
+   //! Ensure that streaming again closes the earlier connection rather than leaving it running
+
+   block_on(async {
+      // GIVEN - a streamer with a live stream
+      let server = MockStreamer::start().await.unwrap();
+      let client = client(&server);
+      let streamer = client.streamer(vec!["AAPL"]);
+      let _first = streamer.stream().await.unwrap();
+
+      // WHEN - we stream again (the server only talks to one client at a time)
+      let mut second = timeout(Duration::from_secs(5), streamer.stream()).await.expect("the first connection was not closed").unwrap();
+      server.quote(&quote("AAPL", 112.5));
+
+      // THEN - the quote comes through the new stream
+      let received = timeout(Duration::from_secs(5), second.next()).await.unwrap().unwrap().unwrap();
+      assert_eq!(112.5, received.price);
+
+      // AND - there were exactly two connections
+      assert_eq!(2, server.connections());
+
+      streamer.stop().await;
+   });
+}
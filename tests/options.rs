@@ -0,0 +1,100 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{client, data_mock};
+use mockito::{Matcher, Mock};
+use tokio_test::block_on;
+
+fn base_mock(test_name: &str, symbol: &str, query: Matcher) -> std::io::Result<Mock> {
+   Ok(data_mock(&format!("/v7/finance/options/{}", symbol), &format!("options_data/{}.json", test_name))?.match_query(query))
+}
+
+fn date(expiration: i64) -> Matcher {
+   Matcher::UrlEncoded("date".to_string(), expiration.to_string())
+}
+
+#[test]
+fn expirations_valid() {
+   //! Ensure that we can list the expiration dates for a symbol
+
+   // GIVEN - a valid response without an expiration date
+   let _m = base_mock("aapl", "AAPL", Matcher::Missing).unwrap().create();
+
+   // WHEN - we load the expiration dates
+   let result = block_on(client().expirations("AAPL")).unwrap();
+
+   // THEN - we get every date, earliest first
+   assert_eq!(vec![Utc.timestamp(1600387200, 0), Utc.timestamp(1600992000, 0)], result);
+}
+
+#[test]
+fn chain_valid() {
+   //! Ensure that we can load the calls and puts for an expiration date
+
+   // GIVEN - a valid response for the expiration date
+   let _m = base_mock("aapl", "AAPL", date(1600387200)).unwrap().create();
+
+   // WHEN - we load the chain
+   let result = block_on(client().chain("AAPL", Utc.timestamp(1600387200, 0))).unwrap();
+
+   // THEN - we get the calls and puts for that date
+   assert_eq!("AAPL", result.underlying_symbol);
+   assert_eq!(Utc.timestamp(1600387200, 0), result.expiration);
+   assert_eq!(2, result.calls.len());
+   assert_eq!(1, result.puts.len());
+
+   // AND - the contracts have their pricing
+   let call = &result.calls[0];
+   assert_eq!("AAPL200918C00110000", call.contract_symbol);
+   assert_eq!(110.0, call.strike);
+   assert_eq!(Some(4.45), call.last_price);
+   assert_eq!(Some(4.35), call.bid);
+   assert_eq!(Some(4.5), call.ask);
+   assert_eq!(Some(35210), call.volume);
+   assert_eq!(Some(64382), call.open_interest);
+   assert_eq!(Some(0.5498), call.implied_volatility);
+   assert!(call.in_the_money);
+   assert_eq!(Some(Utc.timestamp(1600113598, 0)), call.last_trade_date);
+
+   // AND - contracts that never traded have no volume or last trade date
+   let put = &result.puts[0];
+   assert!(!put.in_the_money);
+   assert_eq!(None, put.volume);
+   assert_eq!(None, put.open_interest);
+   assert_eq!(None, put.last_trade_date);
+}
+
+#[test]
+fn chains_valid() {
+   //! Ensure that we can load the chains for every expiration date
+
+   // GIVEN - valid responses for each expiration date
+   let _m1 = base_mock("aapl", "AAPL", Matcher::Missing).unwrap().create();
+   let _m2 = base_mock("aapl", "AAPL", date(1600387200)).unwrap().create();
+   let _m3 = base_mock("aapl_later", "AAPL", date(1600992000)).unwrap().create();
+
+   // WHEN - we load every chain
+   let result = block_on(client().chains("AAPL")).unwrap();
+
+   // THEN - we get a chain per expiration date, in order
+   assert_eq!(2, result.len());
+   assert_eq!(Utc.timestamp(1600387200, 0), result[0].expiration);
+   assert_eq!(2, result[0].calls.len());
+   assert_eq!(Utc.timestamp(1600992000, 0), result[1].expiration);
+   assert_eq!("AAPL200925C00110000", result[1].calls[0].contract_symbol);
+   assert!(result[1].puts.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "MissingData")]
+fn chain_missing() {
+   //! Ensure that a symbol without options fails rather than returning an empty chain
+
+   // GIVEN - a response without any options
+   let _m = base_mock("unknown", "NOPE", Matcher::Missing).unwrap().create();
+
+   // WHEN - we load the nearest chain
+   block_on(client().nearest_chain("NOPE")).unwrap();
+
+   // THEN - we get an error
+}
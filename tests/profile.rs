@@ -1,25 +1,9 @@
-use mockito::{mock, Mock};
-use std::env;
-use std::fs::File;
-use std::io::prelude::*;
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{client, page_mock};
 use tokio_test::block_on;
-use yahoo_finance::{Profile};
-
-fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
-   // Tell the actual code to use a test URL rather than the live one
-   env::set_var("TEST_URL", mockito::server_url());
-
-   // Load the simulated Yahoo data we want to test against
-   let mut file = File::open(format!("tests/profile_data/{}.html", test_name))?;
-   let mut contents = String::new();
-   file.read_to_string(&mut contents)?;
-
-   // Serve up the test data on the test URL
-   Ok(mock("GET", format!("/quote/{symbol}?p={symbol}", symbol=symbol).as_str())
-      .with_header("content-type", "text/html")
-      .with_body(&contents)
-      .with_status(200))
-}
+use yahoo_finance::Profile;
 
 #[test]
 fn load_company() {
@@ -27,10 +11,10 @@ fn load_company() {
 
    // GIVEN - a valid response and stock symbol
    let symbol = "AAPL";
-   let _m = base_mock("aapl", symbol).unwrap().create();
+   let _m = page_mock("profile_data/aapl", symbol).unwrap().create();
 
    // WHEN - we load the data
-   let result = block_on(Profile::load(symbol)).unwrap();
+   let result = block_on(client().profile(symbol)).unwrap();
 
    // THEN - we get the results we expect
    match result {
@@ -50,10 +34,10 @@ fn load_fund() {
 
    // GIVEN - a valid response and stock symbol
    let symbol = "QQQ";
-   let _m = base_mock("qqq", symbol).unwrap().create();
+   let _m = page_mock("profile_data/qqq", symbol).unwrap().create();
 
    // WHEN - we load the data
-   let result = block_on(Profile::load(symbol)).unwrap();
+   let result = block_on(client().profile(symbol)).unwrap();
 
    // THEN - we get the results we expect
    match result {
@@ -67,6 +51,66 @@ fn load_fund() {
    }
 }
 
+#[test]
+fn load_mutual_fund() {
+   //! Ensure that we can load for mutual funds as well as ETFs
+
+   // GIVEN - a valid response and mutual fund symbol
+   let symbol = "VFIAX";
+   let _m = page_mock("profile_data/vfiax", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(client().profile(symbol)).unwrap();
+
+   // THEN - we get a fund profile
+   match result {
+      Profile::Fund(profile) => {
+         assert_eq!("Vanguard 500 Index Fund Admiral Shares", profile.name);
+         assert_eq!("Open-End Fund", profile.kind);
+         assert_eq!(Some(0.0004), profile.expense_ratio);
+      },
+      _ => panic!("Needs to be a fund profile")
+   }
+}
+
+#[test]
+fn load_fund_details() {
+   //! Ensure that we can load the fees, holdings and allocation of a fund
+
+   // GIVEN - a valid response with the fund modules
+   let symbol = "QQQ";
+   let _m = page_mock("profile_data/qqq_holdings", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(client().profile(symbol)).unwrap();
+
+   // THEN - we get the fund details
+   match result {
+      Profile::Fund(profile) => {
+         assert_eq!(Some("Large Growth".to_string()), profile.category);
+         assert_eq!(Some(0.002), profile.expense_ratio);
+         assert_eq!(Some(0.069299996), profile.turnover);
+         assert_eq!(Some(Utc.timestamp(920937600, 0)), profile.inception_date);
+         assert_eq!(Some(145483046912.0), profile.total_assets);
+
+         assert_eq!(2, profile.holdings.len());
+         assert_eq!(Some("AAPL".to_string()), profile.holdings[0].symbol);
+         assert_eq!(Some(0.1294), profile.holdings[0].weight);
+
+         // sectors without a weight are left out
+         assert_eq!(2, profile.sector_weightings.len());
+         assert_eq!("technology", profile.sector_weightings[1].name);
+         assert_eq!(0.4659, profile.sector_weightings[1].weight);
+         assert_eq!(2, profile.bond_ratings.len());
+
+         let allocation = profile.asset_allocation.unwrap();
+         assert_eq!(Some(0.9987), allocation.stock);
+         assert_eq!(Some(0.0013), allocation.cash);
+      },
+      _ => panic!("Needs to be a fund  profile")
+   }
+}
+
 #[test]
 #[should_panic(expected = "BadData")]
 fn load_bad_data() {
@@ -74,10 +118,10 @@ fn load_bad_data() {
 
    // GIVEN - a response that has no data
    let symbol = "NULL";
-   let _m = base_mock("invalid_json", symbol).unwrap().create();
+   let _m = page_mock("profile_data/invalid_json", symbol).unwrap().create();
 
    // WHEN - we load the data
-   block_on(Profile::load(symbol)).expect("failure");
+   block_on(client().profile(symbol)).expect("failure");
 
    // THEN - we get an error
 }
@@ -89,12 +133,12 @@ fn load_bad_response() {
 
    // GIVEN - a symbol that does not exist
    let symbol = "NULL";
-   let _m = base_mock("aapl", symbol).unwrap()
+   let _m = page_mock("profile_data/aapl", symbol).unwrap()
       .with_status(404)
       .create();
 
    // WHEN - we load the data
-   block_on(Profile::load(symbol)).expect("failure");
+   block_on(client().profile(symbol)).expect("failure");
 
    // THEN - we get an error
 }
@@ -106,10 +150,25 @@ fn load_missing_data() {
 
    // GIVEN - a response that has no data
    let symbol = "NULL";
-   let _m = base_mock("missing_data", symbol).unwrap().create();
+   let _m = page_mock("profile_data/missing_data", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(client().profile(symbol)).expect("failure");
+
+   // THEN - we get an error
+}
+
+#[test]
+#[should_panic(expected = "MissingData")]
+fn load_missing_fund_profile() {
+   //! Ensures that we gracefully fail when Yahoo! doesn't return the profile of a fund
+
+   // GIVEN - a fund response without a fund profile
+   let symbol = "QQQ";
+   let _m = page_mock("profile_data/missing_fund_profile", symbol).unwrap().create();
 
    // WHEN - we load the data
-   block_on(Profile::load(symbol)).expect("failure");
+   block_on(client().profile(symbol)).expect("failure");
 
    // THEN - we get an error
 }
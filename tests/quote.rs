@@ -0,0 +1,65 @@
+mod common;
+
+use common::{client, data_mock};
+use mockito::{mock, Matcher, Mock};
+use tokio_test::block_on;
+use yahoo_finance::quote::MarketState;
+
+fn base_mock(test_name: &str, symbols: &str) -> std::io::Result<Mock> {
+   Ok(data_mock("/v7/finance/quote", &format!("quote_data/{}.json", test_name))?
+      .match_query(Matcher::UrlEncoded("symbols".to_string(), symbols.to_string())))
+}
+
+#[test]
+fn snapshot_valid() {
+   //! Ensure that we can load snapshots for many symbols at once
+
+   // GIVEN - a valid response for a stock and an index
+   let _m = base_mock("multi", "AAPL,^DJI").unwrap().create();
+
+   // WHEN - we load the snapshots
+   let result = block_on(client().snapshot(&["AAPL", "^DJI"])).unwrap();
+
+   // THEN - we get the details of each
+   assert_eq!(2, result.len());
+
+   let aapl = &result[0];
+   assert_eq!("AAPL", aapl.symbol);
+   assert_eq!(Some("Apple Inc.".to_string()), aapl.name);
+   assert_eq!(Some(MarketState::PostMarket), aapl.market_state);
+   assert_eq!(Some(120.96), aapl.price);
+   assert_eq!(Some(120.9), aapl.bid);
+   assert_eq!(Some(12), aapl.ask_size);
+   assert_eq!(Some(137.98), aapl.fifty_two_week_high);
+   assert_eq!(Some(2068739309568), aapl.market_cap);
+   assert_eq!(Some(36.54), aapl.trailing_pe);
+   assert_eq!(Some(120.9), aapl.post_market.as_ref().map(|post| post.price));
+   assert!(aapl.pre_market.is_none());
+
+   // AND - the overnight state is closed
+   let dji = &result[1];
+   assert_eq!(Some(MarketState::Closed), dji.market_state);
+
+   // AND - what doesn't apply is left out
+   assert_eq!(Some("INDEX".to_string()), dji.kind);
+   assert!(dji.bid.is_none());
+   assert!(dji.market_cap.is_none());
+}
+
+#[test]
+#[should_panic(expected = "BadData")]
+fn snapshot_bad_data() {
+   //! Ensures that we gracefully fail when Yahoo! sends back bad JSON
+
+   // GIVEN - a response that isn't a quote response
+   let _m = mock("GET", "/v7/finance/quote")
+      .match_query(Matcher::Any)
+      .with_body("{json}")
+      .with_status(200)
+      .create();
+
+   // WHEN - we load the snapshots
+   block_on(client().snapshot(&["NULL"])).unwrap();
+
+   // THEN - we get an error
+}
@@ -0,0 +1,159 @@
+use mockito::{mock, Matcher, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::{Interval, YahooClient};
+
+/// A client that starts its sessions with the mock server rather than the live one
+fn client() -> YahooClient {
+   YahooClient::builder()
+      .chart_url(&mockito::server_url())
+      .cookie_url(&format!("{}/cookie", mockito::server_url()))
+      .crumb_url(&format!("{}/getcrumb", mockito::server_url()))
+      .build()
+      .unwrap()
+}
+
+fn session_mocks() -> (Mock, Mock) {
+   // Yahoo! hands out the cookie on a 404
+   let cookie = mock("GET", "/cookie")
+      .with_header("set-cookie", "B=abc123&b=3&s=sk; Expires=Fri, 10 Sep 2027 16:24:46 GMT; Path=/; Domain=.yahoo.com")
+      .with_status(404);
+
+   // ... and the crumb only goes to callers with the cookie
+   let crumb = mock("GET", "/getcrumb")
+      .match_header("cookie", "B=abc123&b=3&s=sk")
+      .with_body("Xy1.ab/Cd3")
+      .with_status(200);
+
+   (cookie, crumb)
+}
+
+fn chart_mock() -> std::io::Result<Mock> {
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open("tests/history_data/aapl.json")?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data only to calls with the session
+   Ok(mock("GET", "/AAPL")
+      .match_query(Matcher::AllOf(vec![
+         Matcher::UrlEncoded("range".to_string(), "5d".to_string()),
+         Matcher::UrlEncoded("crumb".to_string(), "Xy1.ab/Cd3".to_string())
+      ]))
+      .match_header("cookie", "B=abc123&b=3&s=sk")
+      .with_header("content-type", "application/json")
+      .with_body(&contents))
+}
+
+#[test]
+fn session_attached() {
+   //! Ensure that the session is started once and sent along with every call
+
+   // GIVEN - a server handing out a session
+   let (cookie, crumb) = session_mocks();
+   let cookie = cookie.expect(1).create();
+   let crumb = crumb.expect(1).create();
+   let chart = chart_mock().unwrap().with_status(200).expect(2).create();
+
+   // WHEN - we make a couple of calls with the same client
+   let client = client();
+   block_on(client.history_interval("AAPL", Interval::_5d)).unwrap();
+   let result = block_on(client.history_interval("AAPL", Interval::_5d)).unwrap();
+
+   // THEN - the calls succeed with a single session
+   assert_eq!(5, result.len());
+   cookie.assert();
+   crumb.assert();
+   chart.assert();
+}
+
+#[test]
+fn session_refreshed() {
+   //! Ensure that a new session is started when Yahoo! rejects the current one
+
+   // GIVEN - a server that rejects the session
+   let (cookie, crumb) = session_mocks();
+   let _cookie = cookie.create();
+   let crumb = crumb.expect(2).create();
+   let chart = chart_mock().unwrap().with_status(401).expect(2).create();
+
+   // WHEN - we make a call
+   let result = block_on(client().history_interval("AAPL", Interval::_5d));
+
+   // THEN - we retried with a new session before giving up
+   assert!(format!("{:?}", result.err().unwrap()).contains("CallFailed"));
+   crumb.assert();
+   chart.assert();
+}
+
+#[test]
+fn session_unavailable() {
+   //! Ensure that calls still go out when Yahoo! doesn't hand out a session
+
+   // GIVEN - a server without a cookie
+   let cookie = mock("GET", "/cookie").with_status(404).expect(1).create();
+
+   // AND - a call that works without a session
+   let mut file = File::open("tests/history_data/aapl.json").unwrap();
+   let mut contents = String::new();
+   file.read_to_string(&mut contents).unwrap();
+   let chart = mock("GET", "/AAPL?range=5d&interval=1d")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200)
+      .expect(2)
+      .create();
+
+   // WHEN - we make a couple of calls with the same client
+   let client = client();
+   block_on(client.history_interval("AAPL", Interval::_5d)).unwrap();
+   let result = block_on(client.history_interval("AAPL", Interval::_5d)).unwrap();
+
+   // THEN - the calls succeed without a session - and we only tried to start one once
+   assert_eq!(5, result.len());
+   cookie.assert();
+   chart.assert();
+}
+
+#[test]
+#[should_panic(expected = "SessionFailed")]
+fn session_no_cookie() {
+   //! Ensure that we gracefully fail when Yahoo! insists on a session but doesn't hand out a cookie
+
+   // GIVEN - a server without a cookie that rejects calls without a session
+   let _cookie = mock("GET", "/cookie").with_status(404).create();
+   let _chart = mock("GET", "/AAPL?range=5d&interval=1d").with_status(401).create();
+
+   // WHEN - we make a call
+   block_on(client().history_interval("AAPL", Interval::_5d)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn session_shared() {
+   //! Ensure that concurrent calls wait for a single session rather than each starting their own
+
+   // GIVEN - a server handing out a session
+   let (cookie, crumb) = session_mocks();
+   let cookie = cookie.expect(1).create();
+   let crumb = crumb.expect(1).create();
+   let chart = chart_mock().unwrap().with_status(200).expect(3).create();
+
+   // WHEN - we make several calls at once with the same client
+   let client = client();
+   let results = block_on(async {
+      futures::join!(
+         client.history_interval("AAPL", Interval::_5d),
+         client.history_interval("AAPL", Interval::_5d),
+         client.history_interval("AAPL", Interval::_5d)
+      )
+   });
+
+   // THEN - the calls succeed with a single session
+   assert!(results.0.is_ok() && results.1.is_ok() && results.2.is_ok());
+   cookie.assert();
+   crumb.assert();
+   chart.assert();
+}
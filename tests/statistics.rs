@@ -0,0 +1,68 @@
+mod common;
+
+use chrono::{TimeZone, Utc};
+use common::{client, summary_mock};
+use tokio_test::block_on;
+
+const MODULES: &str = "defaultKeyStatistics,summaryDetail";
+
+#[test]
+fn load_valuation() {
+   //! Ensure that we can load the valuation measures for a company
+
+   // GIVEN - a valid response with key statistics and summary detail
+   let _m = summary_mock("statistics_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the statistics
+   let result = block_on(client().statistics("AAPL")).unwrap();
+
+   // THEN - we get the valuation measures from both modules
+   assert_eq!(Some(1920288653312), result.market_cap);
+   assert_eq!(Some(1985626013696.0), result.enterprise_value);
+   assert_eq!(Some(34.0), result.trailing_pe);
+   assert_eq!(Some(30.512634), result.forward_pe);
+   assert_eq!(Some(2.99), result.peg_ratio);
+   assert_eq!(Some(26.619255), result.price_to_book);
+   assert_eq!(Some(7.0335), result.price_to_sales);
+   assert_eq!(Some(1.28), result.beta);
+}
+
+#[test]
+fn load_shares_and_dividends() {
+   //! Ensure that we can load share counts, short interest and dividends
+
+   // GIVEN - a valid response with key statistics and summary detail
+   let _m = summary_mock("statistics_data/aapl", "AAPL", MODULES).unwrap().create();
+
+   // WHEN - we load the statistics
+   let result = block_on(client().statistics("AAPL")).unwrap();
+
+   // THEN - we get the share counts and short interest
+   assert_eq!(Some(17102499840), result.shares_outstanding);
+   assert_eq!(Some(17057511590), result.float_shares);
+   assert_eq!(Some(150087164), result.shares_short);
+   assert_eq!(Some(0.0088), result.short_percent_of_float);
+
+   // AND - the dividends and trading ranges
+   assert_eq!(Some(0.0077), result.dividend_yield);
+   assert_eq!(Some(0.2408), result.payout_ratio);
+   assert_eq!(Some(Utc.timestamp(1596758400, 0)), result.ex_dividend_date);
+   assert_eq!(Some(53.1525), result.fifty_two_week_low);
+   assert_eq!(Some(137.98), result.fifty_two_week_high);
+}
+
+#[test]
+fn load_missing_modules() {
+   //! Ensure that symbols without statistics simply have none
+
+   // GIVEN - a response without key statistics or summary detail
+   let _m = summary_mock("statistics_data/qqq", "QQQ", MODULES).unwrap().create();
+
+   // WHEN - we load the statistics
+   let result = block_on(client().statistics("QQQ")).unwrap();
+
+   // THEN - every value is empty
+   assert_eq!(None, result.market_cap);
+   assert_eq!(None, result.trailing_pe);
+   assert_eq!(None, result.beta);
+}
@@ -0,0 +1,127 @@
+use futures::StreamExt;
+use std::net::SocketAddr;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio::time::timeout;
+use tokio_test::block_on;
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::Message;
+use yahoo_finance::{ConnectionState, ReconnectPolicy, StreamEvent, YahooClient};
+
+/// Accepts a single websocket connection, collecting what the client sends until it closes the connection
+async fn server() -> (SocketAddr, JoinHandle<Vec<Message>>) {
+   let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+   let addr = listener.local_addr().unwrap();
+
+   let received = tokio::spawn(async move {
+      let (socket, _) = listener.accept().await.unwrap();
+      let mut socket = accept_async(socket).await.unwrap();
+
+      let mut received = Vec::new();
+      while let Some(Ok(msg)) = socket.next().await {
+         let closing = msg.is_close();
+         received.push(msg);
+         if closing { break; }
+      }
+      received
+   });
+
+   (addr, received)
+}
+
+#[test]
+fn subscriptions_changed() {
+   //! Ensure that subscribing and unsubscribing keeps track of the symbols
+
+   // GIVEN - a streamer for a couple of symbols
+   let streamer = YahooClient::new().unwrap().streamer(vec!["AAPL", "QQQ"]);
+
+   // WHEN - we change the subscriptions before streaming
+   streamer.subscribe(vec!["MSFT", "AAPL"]);
+   streamer.unsubscribe(vec!["QQQ", "^DJI"]);
+
+   // THEN - only the current symbols are subscribed - once each
+   assert_eq!(vec!["AAPL".to_string(), "MSFT".to_string()], streamer.subscriptions());
+}
+
+#[test]
+fn resilient_unreachable_gives_up() {
+   //! Ensure that a resilient stream reports its reconnects and gives up after the last attempt
+
+   // GIVEN - a streamer pointing at a server that isn't there
+   let client = YahooClient::builder().streamer_url("ws://127.0.0.1:1/").build().unwrap();
+   let streamer = client.streamer(vec!["AAPL"]);
+   let policy = ReconnectPolicy { base_delay: Duration::from_millis(10), max_delay: Duration::from_millis(10), max_attempts: Some(1), ..ReconnectPolicy::default() };
+
+   // WHEN - we stream until the streamer gives up
+   let events = block_on(async { streamer.stream_resilient(policy).await.collect::<Vec<_>>().await });
+   let states = events.into_iter()
+      .map(|event| match event {
+         StreamEvent::State(ConnectionState::Disconnected { .. }) => "disconnected",
+         StreamEvent::State(ConnectionState::Reconnecting { attempt: 1, .. }) => "reconnecting",
+         StreamEvent::State(ConnectionState::Connecting) => "connecting",
+         _ => "unexpected"
+      })
+      .collect::<Vec<_>>();
+
+   // THEN - we see each connection attempt fail, with a single reconnect in between
+   assert_eq!(vec!["connecting", "disconnected", "reconnecting", "connecting", "disconnected"], states);
+}
+
+#[test]
+fn stream_connect_failed() {
+   //! Ensure that failing to connect is an error rather than a panic
+
+   // GIVEN - a streamer pointing at a server that isn't there
+   let client = YahooClient::builder().streamer_url("ws://127.0.0.1:1/").build().unwrap();
+   let streamer = client.streamer(vec!["AAPL"]);
+
+   // WHEN - we start streaming
+   let result = block_on(async { streamer.stream().await.map(|_| ()) });
+
+   // THEN - we get an error
+   let err = result.unwrap_err();
+   assert!(format!("{:?}", err).contains("StreamFailed"));
+}
+
+#[test]
+fn stop_closes() {
+   //! Ensure that stopping closes the connection and waits for the writer to finish
+
+   block_on(async {
+      // GIVEN - a streamer that is connected
+      let (addr, received) = server().await;
+      let client = YahooClient::builder().streamer_url(&format!("ws://{}/", addr)).build().unwrap();
+      let streamer = client.streamer(vec!["AAPL"]);
+      let _stream = streamer.stream().await.unwrap();
+
+      // WHEN - we stop the streamer
+      timeout(Duration::from_secs(5), streamer.stop()).await.expect("the writer did not finish");
+
+      // THEN - the server got the subscription followed by a close
+      let received = timeout(Duration::from_secs(5), received).await.unwrap().unwrap();
+      assert_eq!(Some(&Message::Text(r#"{"subscribe":["AAPL"]}"#.to_string())), received.first());
+      assert!(received.last().unwrap().is_close());
+   });
+}
+
+#[test]
+fn drop_closes() {
+   //! Ensure that dropping the stream closes the connection
+
+   block_on(async {
+      // GIVEN - a streamer that is connected
+      let (addr, received) = server().await;
+      let client = YahooClient::builder().streamer_url(&format!("ws://{}/", addr)).build().unwrap();
+      let streamer = client.streamer(vec!["AAPL"]);
+      let stream = streamer.stream().await.unwrap();
+
+      // WHEN - we drop the stream
+      drop(stream);
+
+      // THEN - the server gets a close
+      let received = timeout(Duration::from_secs(5), received).await.expect("the connection was not closed").unwrap();
+      assert!(received.last().unwrap().is_close());
+   });
+}